@@ -46,17 +46,17 @@ fn print_transactions_with_details(transactions: TransactionList) {
         println!("  Account ID: {}", tr.account_id);
         println!(
             "  Archive ID: {}",
-            tr.archive_id.unwrap_or_else(|| NONE_STRING.to_string())
+            tr.archive_id.as_deref().unwrap_or(NONE_STRING)
         );
         println!(
             "  Reference: {}",
-            tr.reference.unwrap_or_else(|| NONE_STRING.to_string())
+            tr.reference.as_deref().unwrap_or(NONE_STRING)
         );
         println!(
             "  Message: {}",
-            tr.message.unwrap_or_else(|| NONE_STRING.to_string())
+            tr.message.as_deref().unwrap_or(NONE_STRING)
         );
-        println!("  Amount: {}{}", tr.amount, tr.currency);
+        println!("  Amount: {}", tr.amount_money());
         println!("  Type: {}", tr.credit_debit_indicator);
         println!("  Account balance: {}", tr.account_balance);
         println!("  Creditor:");
@@ -67,7 +67,9 @@ fn print_transactions_with_details(transactions: TransactionList) {
         println!("  Balanced: {}", tr.value_datetaime);
         println!(
             "  Status: {}",
-            tr.status.unwrap_or_else(|| "UNKNOWN".to_string())
+            tr.status
+                .map(String::from)
+                .unwrap_or_else(|| "UNKNOWN".to_string())
         );
     }
 }
@@ -84,12 +86,13 @@ async fn print_accounts_with_details(client: &Client, accounts: AccountList) {
         println!("Name: {}", acc.name);
         println!(
             "Nickname: {}",
-            acc.nickname.unwrap_or_else(|| NONE_STRING.to_string())
+            acc.nickname.as_deref().unwrap_or(NONE_STRING)
         );
         println!(
-            "Balance: {}{}",
-            acc.balance.unwrap_or(std::f64::NAN),
-            acc.currency
+            "Balance: {}",
+            acc.balance_money()
+                .map(|balance| balance.to_string())
+                .unwrap_or_else(|| NONE_STRING.to_string())
         );
         println!("IBAN: {}", acc.identifier);
         println!("Last 5 transactions:");
@@ -120,7 +123,6 @@ async fn main() {
     }
 
     let options = Options::new_dev(args[1].clone());
-    options.set_version("v3".to_string());
     let client = Client::new(options);
     let resp = client.accounts().await;
     match resp {
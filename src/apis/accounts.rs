@@ -2,13 +2,44 @@
 //! [AccountsV3](https://op-developer.fi/docs/api/3Oo5zCujXGw2SGEi00skug/OP%20Accounts%20V3.0%20API)
 //! API
 
+use crate::error::{Result, SdkError};
 use crate::model::accounts::*;
-use crate::options::Options;
+use crate::options::{Api, Options};
 use crate::requests::Requests;
-use log::debug;
-use std::error::Error;
+use chrono::NaiveDateTime;
+use futures::stream::{self, Stream, TryStreamExt};
+use log::{debug, warn};
+use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
 
+/// Version of the AccountsV3 API this client is implemented against.
+pub const DEFAULT_VERSION: &str = "v3";
+
+/// State of the paginating transactions stream.
+struct TransactionPager {
+    account_id: String,
+    params: Option<TransactionParams>,
+    limits: PagingLimits,
+    buffer: VecDeque<Transaction>,
+    tokens: HashSet<String>,
+    pages: usize,
+    items: usize,
+    has_more: bool,
+}
+
+/// Implementation of the TransactionPager.
+impl TransactionPager {
+    /// Returns true if no more transactions should be returned.
+    fn items_exhausted(&self) -> bool {
+        self.limits.max_items.is_some_and(|max| self.items >= max)
+    }
+
+    /// Returns true if no more pages should be fetched.
+    fn pages_exhausted(&self) -> bool {
+        !self.has_more || self.limits.max_pages.is_some_and(|max| self.pages >= max)
+    }
+}
+
 /// Accounts client.
 ///
 /// This client is used to access the OP AccountsV3 API.
@@ -19,31 +50,31 @@ pub struct AccountsApi {
 impl AccountsApi {
     /// Creates new Accounts API.
     ///
-    /// Bear in mind that this API is implemented to follow v3 so you must
-    /// specify v3 as version for the Options.
+    /// This API is implemented to follow v3 which is used by default.
+    /// The version can be overridden with Options::set_api_version.
     pub fn new(options: Arc<Options>) -> AccountsApi {
         AccountsApi { options }
     }
 
     /// Gets all accounts from the API and returns list of them.
-    pub async fn accounts(&self) -> Result<AccountList, Box<dyn Error>> {
-        let url = format!("/accounts/{}/accounts", self.options.version());
-        let response = Requests::get(&self.options, &url, None::<()>).await?;
-        debug!("Accounts response: {:#?}", response);
-        let accounts: AccountList = response.json().await?;
+    pub async fn accounts(&self) -> Result<AccountList> {
+        let options = self.options.snapshot();
+        let url = format!("/accounts/{}/accounts", options.api_version(Api::Accounts));
+        let accounts: AccountList = Requests::get(&options, &url, None::<()>).await?;
+        debug!("Accounts response: {:#?}", accounts);
         Ok(accounts)
     }
 
     /// Gets single account from the API based on accountId.
-    pub async fn account(&self, account_id: String) -> Result<Account, Box<dyn Error>> {
+    pub async fn account(&self, account_id: String) -> Result<Account> {
+        let options = self.options.snapshot();
         let url = format!(
             "/accounts/{}/accounts/{}",
-            self.options.version(),
+            options.api_version(Api::Accounts),
             account_id
         );
-        let response = Requests::get(&self.options, &url, None::<()>).await?;
-        debug!("Account response: {:#?}", response);
-        let account: Account = response.json().await?;
+        let account: Account = Requests::get(&options, &url, None::<()>).await?;
+        debug!("Account response: {:#?}", account);
         Ok(account)
     }
 
@@ -53,15 +84,145 @@ impl AccountsApi {
         &self,
         account_id: String,
         params: Option<TransactionParams>,
-    ) -> Result<TransactionList, Box<dyn Error>> {
+    ) -> Result<TransactionList> {
+        let options = self.options.snapshot();
         let url = format!(
             "/accounts/{}/accounts/{}/transactions",
-            self.options.version(),
+            options.api_version(Api::Accounts),
             account_id
         );
-        let response = Requests::get(&self.options, &url, params).await?;
-        debug!("Transactions response: {:#?}", response);
-        let list: TransactionList = response.json().await?;
+        let list: TransactionList = Requests::get(&options, &url, params).await?;
+        debug!("Transactions response: {:#?}", list);
         Ok(list)
     }
+
+    /// Returns stream of all transactions for a single account with
+    /// account id with optional parameters for filtering the results.
+    ///
+    /// Pages are fetched lazily following the next links of the
+    /// responses until there are no more pages or the limits are reached.
+    /// Empty pages with a next link are skipped. The stream ends after
+    /// the first error or if the API returns a paging token which has
+    /// already been requested.
+    pub fn transactions_stream(
+        &self,
+        account_id: String,
+        params: Option<TransactionParams>,
+        limits: PagingLimits,
+    ) -> impl Stream<Item = Result<Transaction>> + '_ {
+        let tokens = params
+            .as_ref()
+            .and_then(|p| p.forward_paging_token.clone())
+            .into_iter()
+            .collect();
+        let pager = TransactionPager {
+            account_id,
+            params,
+            limits,
+            buffer: VecDeque::new(),
+            tokens,
+            pages: 0,
+            items: 0,
+            has_more: true,
+        };
+        stream::unfold(pager, move |mut pager| async move {
+            if pager.items_exhausted() {
+                return None;
+            }
+            while pager.buffer.is_empty() {
+                if pager.pages_exhausted() {
+                    return None;
+                }
+                let page = self
+                    .transactions(pager.account_id.clone(), pager.params.clone())
+                    .await;
+                let page = match page {
+                    Ok(page) => page,
+                    Err(e) => {
+                        pager.has_more = false;
+                        pager.buffer.clear();
+                        return Some((Err(e), pager));
+                    }
+                };
+                pager.pages += 1;
+                match page.next_page_token() {
+                    Some(token) if pager.tokens.contains(&token) => {
+                        warn!("Paging token {} repeated, ending transactions", token);
+                        pager.has_more = false;
+                    }
+                    Some(token) => {
+                        pager.tokens.insert(token.clone());
+                        let params = pager.params.take().unwrap_or_default();
+                        pager.params = Some(params.with_forward_paging_token(token));
+                    }
+                    None => pager.has_more = false,
+                }
+                debug!(
+                    "Fetched page {} with {} transactions",
+                    pager.pages,
+                    page.transactions.len()
+                );
+                pager.buffer.extend(page.transactions);
+            }
+            let transaction = pager.buffer.pop_front()?;
+            pager.items += 1;
+            Some((Ok(transaction), pager))
+        })
+    }
+
+    /// Gets all transactions booked between local date-times from and to
+    /// for a single account with account id.
+    ///
+    /// The range is split into windows defined by the options and every
+    /// window is fetched page by page. Transactions returned by more than
+    /// one window are included only once and the result is ordered by
+    /// booking date-time.
+    pub async fn transaction_history(
+        &self,
+        account_id: String,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        options: HistoryOptions,
+    ) -> Result<Vec<Transaction>> {
+        if from > to {
+            return Err(SdkError::Configuration(format!(
+                "Start of the transaction history {} is after the end {}",
+                from, to
+            )));
+        }
+        if options.window <= chrono::Duration::zero() {
+            return Err(SdkError::Configuration(
+                "Transaction history window must be positive".to_string(),
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        let mut history = Vec::new();
+        let mut start = from;
+        loop {
+            let end = start
+                .checked_add_signed(options.window)
+                .map_or(to, |end| std::cmp::min(end, to));
+            let mut params = TransactionParams::default()
+                .with_from_booking_datetime(start)
+                .with_to_booking_datetime(end);
+            params.page_size = options.page_size;
+            debug!("Fetching transactions from {} to {}", start, end);
+            let window: Vec<Transaction> = self
+                .transactions_stream(account_id.clone(), Some(params), PagingLimits::default())
+                .try_collect()
+                .await?;
+            history.extend(
+                window
+                    .into_iter()
+                    .filter(|t| seen.insert(t.transaction_id.clone())),
+            );
+            if end >= to {
+                break;
+            }
+            start = end;
+        }
+        history.sort_by_key(|t| t.booking_datetime);
+        Ok(history)
+    }
 }
@@ -2,13 +2,16 @@
 //! [Funds data V1](https://op-developer.fi/docs/api/5lWcjqy3JY2G2y4UGmS6Yw/Funds-data#operation/getFunds)
 //! API
 
+use crate::error::Result;
 use crate::model::funds::*;
-use crate::options::Options;
+use crate::options::{Api, Options};
 use crate::requests::Requests;
 use log::debug;
-use std::error::Error;
 use std::sync::Arc;
 
+/// Version of the FundsV1 API this client is implemented against.
+pub const DEFAULT_VERSION: &str = "v1";
+
 /// Funds client.
 ///
 /// This client is used to access the OP FundsV1 API.
@@ -19,18 +22,18 @@ pub struct FundsApi {
 impl FundsApi {
     /// Creates new Funds API.
     ///
-    /// Bear in mind that this API is implemented to follow v1 so you must
-    /// specify v1 as version for the Options.
+    /// This API is implemented to follow v1 which is used by default.
+    /// The version can be overridden with Options::set_api_version.
     pub fn new(options: Arc<Options>) -> FundsApi {
         FundsApi { options }
     }
 
     /// Gets all funds from the API and returns them.
-    pub async fn funds(&self) -> Result<Funds, Box<dyn Error>> {
-        let url = format!("/{}/funds", self.options.version());
-        let response = Requests::get(&self.options, &url, None::<()>).await?;
-        debug!("Funds response: {:#?}", response);
-        let funds: Funds = response.json().await?;
+    pub async fn funds(&self) -> Result<Funds> {
+        let options = self.options.snapshot();
+        let url = format!("/{}/funds", options.api_version(Api::Funds));
+        let funds: Funds = Requests::get(&options, &url, None::<()>).await?;
+        debug!("Funds response: {:#?}", funds);
         Ok(funds)
     }
 }
@@ -2,13 +2,16 @@
 //! [HoldingsV1](https://op-developer.fi/docs/api/3VZiIRoT2EowKC6yeA0gqQ/Holdings)
 //! API
 
+use crate::error::Result;
 use crate::model::holdings::HoldingsInformation;
-use crate::options::Options;
+use crate::options::{Api, Options};
 use crate::requests::Requests;
 use log::debug;
-use std::error::Error;
 use std::sync::Arc;
 
+/// Version of the HoldingsV1 API this client is implemented against.
+pub const DEFAULT_VERSION: &str = "v1";
+
 /// Holdings client.
 ///
 /// This client is used to access the OP HoldingsV1 API.
@@ -19,18 +22,21 @@ pub struct HoldingsApi {
 impl HoldingsApi {
     /// Creates new Holdings API.
     ///
-    /// Bear in mind that this API is implemented to follow v1 so you must
-    /// specify v1 as version for the Options.
+    /// This API is implemented to follow v1 which is used by default.
+    /// The version can be overridden with Options::set_api_version.
     pub fn new(options: Arc<Options>) -> HoldingsApi {
         HoldingsApi { options }
     }
 
     /// Gets holdings information from the API.
-    pub async fn holdings(&self) -> Result<HoldingsInformation, Box<dyn Error>> {
-        let url = format!("/holdings/info/{}/holdings", self.options.version());
-        let response = Requests::get(&self.options, &url, None::<()>).await?;
-        debug!("Holdings response: {:#?}", response);
-        let holdings: HoldingsInformation = response.json().await?;
+    pub async fn holdings(&self) -> Result<HoldingsInformation> {
+        let options = self.options.snapshot();
+        let url = format!(
+            "/holdings/info/{}/holdings",
+            options.api_version(Api::Holdings)
+        );
+        let holdings: HoldingsInformation = Requests::get(&options, &url, None::<()>).await?;
+        debug!("Holdings response: {:#?}", holdings);
         Ok(holdings)
     }
 }
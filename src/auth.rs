@@ -0,0 +1,502 @@
+//! This module contains helpers for obtaining user authorization with
+//! the OAuth2 authorization code flow and PKCE as required by the
+//! production [OP API](https://op-developer.fi/docs).
+//!
+//! The flow goes as follows:
+//!
+//! 1. Create an AuthorizationRequest with OAuthClient and redirect the
+//!    user to its URL. Store the state and PKCE verifier.
+//! 2. After the user has authorized the application, OP redirects back
+//!    to the redirect URI with code and state query parameters.
+//! 3. Check that the state matches and exchange the code to a TokenSet
+//!    with OAuthClient::exchange_code.
+//! 4. Use the access token of the TokenSet as authorization in Options
+//!    or set RefreshingTokenProvider to the Options to keep the token
+//!    valid automatically.
+
+use crate::error::{Result, SdkError};
+use crate::options::{Options, OptionsSnapshot};
+use crate::requests::Requests;
+use crate::transport::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use log::debug;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use reqwest::header::HeaderMap;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Default authorization endpoint of OP.
+pub const DEFAULT_AUTHORIZATION_URL: &str = "https://authorize.op.fi/oauth/authorize";
+
+/// Default token endpoint of OP.
+pub const DEFAULT_TOKEN_URL: &str = "https://authorize.op.fi/oauth/token";
+
+/// Length of the generated PKCE code verifier.
+const PKCE_VERIFIER_LENGTH: usize = 64;
+
+/// Length of the generated state parameter.
+const STATE_LENGTH: usize = 32;
+
+/// Default time before expiry when the access token is refreshed.
+const DEFAULT_REFRESH_MARGIN_SECONDS: i64 = 60;
+
+/// Configuration of the OAuth2 client registered in OP-Developer portal.
+#[derive(Debug, Clone)]
+pub struct OAuthConfig {
+    /// Client ID of the application.
+    pub client_id: String,
+    /// Client secret of the application if it is a confidential client.
+    pub client_secret: Option<String>,
+    /// Redirect URI registered for the application.
+    pub redirect_uri: String,
+    /// Scopes requested from the user.
+    pub scopes: Vec<String>,
+    /// URL of the authorization endpoint.
+    pub authorization_url: String,
+    /// URL of the token endpoint.
+    pub token_url: String,
+}
+
+/// Implementation of the OAuthConfig.
+impl OAuthConfig {
+    /// Creates new configuration using the default OP endpoints.
+    pub fn new(client_id: String, redirect_uri: String) -> OAuthConfig {
+        OAuthConfig {
+            client_id,
+            client_secret: None,
+            redirect_uri,
+            scopes: Vec::new(),
+            authorization_url: String::from(DEFAULT_AUTHORIZATION_URL),
+            token_url: String::from(DEFAULT_TOKEN_URL),
+        }
+    }
+
+    /// Sets client secret.
+    pub fn with_client_secret(mut self, client_secret: String) -> Self {
+        self.client_secret = Some(client_secret);
+        self
+    }
+
+    /// Sets requested scopes.
+    pub fn with_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Sets URL of the authorization endpoint.
+    pub fn with_authorization_url(mut self, url: String) -> Self {
+        self.authorization_url = url;
+        self
+    }
+
+    /// Sets URL of the token endpoint.
+    ///
+    /// This is useful when testing together with local stand-in token
+    /// endpoint.
+    pub fn with_token_url(mut self, url: String) -> Self {
+        self.token_url = url;
+        self
+    }
+}
+
+/// PKCE code verifier and challenge as defined in RFC 7636.
+#[derive(Debug, Clone)]
+pub struct PkceChallenge {
+    /// Random code verifier sent with the token request.
+    pub verifier: String,
+    /// S256 challenge of the verifier sent with the authorization request.
+    pub challenge: String,
+}
+
+/// Implementation of the PkceChallenge.
+impl PkceChallenge {
+    /// Generates new random verifier and its challenge.
+    pub fn new() -> PkceChallenge {
+        PkceChallenge::from_verifier(random_string(PKCE_VERIFIER_LENGTH))
+    }
+
+    /// Creates challenge for an existing verifier.
+    pub fn from_verifier(verifier: String) -> PkceChallenge {
+        let digest = Sha256::digest(verifier.as_bytes());
+        let challenge = base64::encode_config(digest, base64::URL_SAFE_NO_PAD);
+        PkceChallenge {
+            verifier,
+            challenge,
+        }
+    }
+
+    /// Returns challenge method used by the challenge.
+    pub fn method(&self) -> &'static str {
+        "S256"
+    }
+}
+
+impl Default for PkceChallenge {
+    fn default() -> Self {
+        PkceChallenge::new()
+    }
+}
+
+/// Authorization request the user is redirected to.
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    /// URL of the authorization endpoint with all parameters.
+    pub url: Url,
+    /// Random state that must match the state in the redirect.
+    pub state: String,
+    /// PKCE verifier that must be given when exchanging the code.
+    pub pkce_verifier: String,
+}
+
+/// Tokens received from the token endpoint.
+///
+/// Debug output of the TokenSet does not contain the tokens themselves.
+#[derive(Clone)]
+pub struct TokenSet {
+    /// Access token used as authorization for the API requests.
+    pub access_token: String,
+    /// Type of the token, usually "Bearer".
+    pub token_type: String,
+    /// Refresh token for getting new access token if available.
+    pub refresh_token: Option<String>,
+    /// Time when the access token expires if known.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Scopes granted by the user.
+    pub scope: Option<String>,
+    /// OpenID Connect ID token if requested.
+    pub id_token: Option<String>,
+}
+
+/// Implement Debug for TokenSet without exposing the tokens.
+impl fmt::Debug for TokenSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TokenSet")
+            .field("token_type", &self.token_type)
+            .field("has_refresh_token", &self.refresh_token.is_some())
+            .field("expires_at", &self.expires_at)
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+/// Implementation of the TokenSet.
+impl TokenSet {
+    /// Returns true if the access token expires within the given margin.
+    ///
+    /// Tokens without known expiry time are never considered expired.
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        match self.expires_at {
+            Some(expires_at) => expires_at - margin <= Utc::now(),
+            None => false,
+        }
+    }
+
+    /// Returns true if the access token has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires_within(Duration::zero())
+    }
+}
+
+/// Token endpoint response as defined in RFC 6749.
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    token_type: String,
+    expires_in: Option<i64>,
+    refresh_token: Option<String>,
+    scope: Option<String>,
+    id_token: Option<String>,
+}
+
+/// Returns expiry time of a token valid for the given seconds.
+///
+/// Negative lifetimes are treated as already expired and lifetimes too
+/// large to represent as no expiry.
+fn expires_at(expires_in: i64) -> Option<DateTime<Utc>> {
+    let lifetime = Duration::try_seconds(expires_in.max(0))?;
+    Utc::now().checked_add_signed(lifetime)
+}
+
+impl From<TokenResponse> for TokenSet {
+    fn from(response: TokenResponse) -> Self {
+        TokenSet {
+            access_token: response.access_token,
+            token_type: response.token_type,
+            refresh_token: response.refresh_token,
+            expires_at: response.expires_in.and_then(expires_at),
+            scope: response.scope,
+            id_token: response.id_token,
+        }
+    }
+}
+
+/// Parameters of the authorization code token request.
+#[derive(Serialize)]
+struct CodeTokenRequest<'a> {
+    grant_type: &'a str,
+    code: &'a str,
+    redirect_uri: &'a str,
+    client_id: &'a str,
+    code_verifier: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_secret: Option<&'a str>,
+}
+
+/// Parameters of the refresh token request.
+#[derive(Serialize)]
+struct RefreshTokenRequest<'a> {
+    grant_type: &'a str,
+    refresh_token: &'a str,
+    client_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_secret: Option<&'a str>,
+}
+
+/// Generates random alphanumeric string.
+fn random_string(length: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .collect()
+}
+
+/// OAuth2 client.
+///
+/// This client is used to get tokens from the OP authorization server.
+/// Token requests are sent with the transport of the options at the
+/// time the client is created.
+pub struct OAuthClient {
+    options: OptionsSnapshot,
+    config: OAuthConfig,
+}
+
+impl OAuthClient {
+    /// Creates new OAuth2 client.
+    ///
+    /// The client keeps its own snapshot of the options without the
+    /// token provider, so the client can be given to a
+    /// RefreshingTokenProvider set to the same options without creating
+    /// a reference cycle. Later changes to the options are not visible
+    /// to the client.
+    pub fn new(options: Arc<Options>, config: OAuthConfig) -> OAuthClient {
+        OAuthClient {
+            options: options.snapshot().without_token_provider(),
+            config,
+        }
+    }
+
+    /// Returns configuration of this client.
+    pub fn config(&self) -> &OAuthConfig {
+        &self.config
+    }
+
+    /// Creates new authorization request with random state and PKCE
+    /// challenge.
+    pub fn authorization_request(&self) -> Result<AuthorizationRequest> {
+        self.authorization_request_with(random_string(STATE_LENGTH), PkceChallenge::new())
+    }
+
+    /// Creates new authorization request with given state and PKCE
+    /// challenge.
+    pub fn authorization_request_with(
+        &self,
+        state: String,
+        pkce: PkceChallenge,
+    ) -> Result<AuthorizationRequest> {
+        let mut url = Url::parse(&self.config.authorization_url).map_err(|e| {
+            SdkError::Configuration(format!(
+                "Invalid authorization URL {}: {}",
+                self.config.authorization_url, e
+            ))
+        })?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", &self.config.redirect_uri)
+            .append_pair("scope", &self.config.scopes.join(" "))
+            .append_pair("state", &state)
+            .append_pair("code_challenge", &pkce.challenge)
+            .append_pair("code_challenge_method", pkce.method());
+        Ok(AuthorizationRequest {
+            url,
+            state,
+            pkce_verifier: pkce.verifier,
+        })
+    }
+
+    /// Exchanges the authorization code from the redirect to tokens.
+    ///
+    /// The PKCE verifier must be the one from the AuthorizationRequest
+    /// the user was redirected to.
+    pub async fn exchange_code(&self, code: &str, pkce_verifier: &str) -> Result<TokenSet> {
+        let request = CodeTokenRequest {
+            grant_type: "authorization_code",
+            code,
+            redirect_uri: &self.config.redirect_uri,
+            client_id: &self.config.client_id,
+            code_verifier: pkce_verifier,
+            client_secret: self.config.client_secret.as_deref(),
+        };
+        let response: TokenResponse = Requests::post_form(
+            &self.options,
+            &self.config.token_url,
+            &request,
+            HeaderMap::new(),
+        )
+        .await?;
+        debug!("Received tokens of type {}", response.token_type);
+        Ok(response.into())
+    }
+
+    /// Gets new tokens with the refresh token.
+    ///
+    /// If the token endpoint does not return a new refresh token, the
+    /// given one is kept in the returned TokenSet.
+    pub async fn refresh_token(&self, refresh_token: &str) -> Result<TokenSet> {
+        let request = RefreshTokenRequest {
+            grant_type: "refresh_token",
+            refresh_token,
+            client_id: &self.config.client_id,
+            client_secret: self.config.client_secret.as_deref(),
+        };
+        let response: TokenResponse = Requests::post_form(
+            &self.options,
+            &self.config.token_url,
+            &request,
+            HeaderMap::new(),
+        )
+        .await?;
+        debug!("Refreshed tokens of type {}", response.token_type);
+        let mut tokens: TokenSet = response.into();
+        if tokens.refresh_token.is_none() {
+            tokens.refresh_token = Some(refresh_token.to_string());
+        }
+        Ok(tokens)
+    }
+}
+
+/// Provider of access tokens for the API requests.
+///
+/// Requests ask the provider for a valid access token before each
+/// request. Use the re-exported async_trait attribute from the
+/// transport module when implementing this trait.
+#[async_trait]
+pub trait TokenProvider: Send + Sync {
+    /// Returns valid access token without 'Bearer' included.
+    async fn access_token(&self) -> Result<String>;
+
+    /// Called when the API rejected the access token with HTTP 401.
+    ///
+    /// Returns true if the provider can give a different token, in which
+    /// case the request is sent once more with a new token from
+    /// access_token.
+    async fn invalidate(&self, rejected_token: &str) -> bool;
+}
+
+/// Token provider always returning the same access token.
+#[derive(Clone)]
+pub struct StaticTokenProvider {
+    access_token: String,
+}
+
+impl StaticTokenProvider {
+    /// Creates new provider for the access token.
+    pub fn new(access_token: String) -> StaticTokenProvider {
+        StaticTokenProvider { access_token }
+    }
+}
+
+#[async_trait]
+impl TokenProvider for StaticTokenProvider {
+    async fn access_token(&self) -> Result<String> {
+        Ok(self.access_token.clone())
+    }
+
+    async fn invalidate(&self, _rejected_token: &str) -> bool {
+        false
+    }
+}
+
+/// Token provider refreshing the access token with the refresh token.
+///
+/// The token is refreshed proactively when it is about to expire and
+/// reactively when the API rejects it. Concurrent requests wait for a
+/// single refresh instead of refreshing the token multiple times. If
+/// the proactive refresh fails, the cached token is used until it has
+/// actually expired.
+pub struct RefreshingTokenProvider {
+    client: OAuthClient,
+    tokens: Mutex<TokenSet>,
+    refresh_margin: Duration,
+}
+
+impl RefreshingTokenProvider {
+    /// Creates new provider with tokens from OAuthClient::exchange_code.
+    ///
+    /// The TokenSet must contain refresh token for the access token
+    /// to be refreshed.
+    pub fn new(client: OAuthClient, tokens: TokenSet) -> RefreshingTokenProvider {
+        RefreshingTokenProvider {
+            client,
+            tokens: Mutex::new(tokens),
+            refresh_margin: Duration::seconds(DEFAULT_REFRESH_MARGIN_SECONDS),
+        }
+    }
+
+    /// Sets how long before expiry the access token is refreshed.
+    pub fn with_refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = margin;
+        self
+    }
+
+    /// Returns current tokens, for example to persist the refresh token.
+    pub async fn tokens(&self) -> TokenSet {
+        self.tokens.lock().await.clone()
+    }
+
+    /// Refreshes the tokens in place.
+    async fn refresh(&self, tokens: &mut TokenSet) -> Result<()> {
+        let refresh_token = tokens.refresh_token.clone().ok_or_else(|| {
+            SdkError::Configuration("No refresh token available for refreshing".to_string())
+        })?;
+        *tokens = self.client.refresh_token(&refresh_token).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TokenProvider for RefreshingTokenProvider {
+    async fn access_token(&self) -> Result<String> {
+        let mut tokens = self.tokens.lock().await;
+        if tokens.expires_within(self.refresh_margin) {
+            debug!("Access token is about to expire, refreshing");
+            if let Err(e) = self.refresh(&mut tokens).await {
+                if tokens.is_expired() {
+                    return Err(e);
+                }
+                debug!("Failed to refresh access token, using cached one: {}", e);
+            }
+        }
+        Ok(tokens.access_token.clone())
+    }
+
+    async fn invalidate(&self, rejected_token: &str) -> bool {
+        let mut tokens = self.tokens.lock().await;
+        if tokens.access_token != rejected_token {
+            // Token was already refreshed by another request
+            return true;
+        }
+        match self.refresh(&mut tokens).await {
+            Ok(()) => true,
+            Err(e) => {
+                debug!("Failed to refresh rejected access token: {}", e);
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use super::{Bic, Iban};
+
+/// Country code of Finnish IBANs.
+const FINLAND: &str = "FI";
+
+/// Finnish bank identified by the leading digits of the account number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinnishBank {
+    /// Leading digits of the national account number used by the bank.
+    pub prefix: &'static str,
+    /// Name of the bank.
+    pub name: &'static str,
+    /// BIC of the bank, parsed by FinnishBank::bic.
+    bic: &'static str,
+}
+
+/// Implementation of the FinnishBank.
+impl FinnishBank {
+    /// Returns BIC of the bank.
+    pub fn bic(&self) -> Bic {
+        Bic::parse(self.bic).expect("Embedded BIC is valid")
+    }
+}
+
+/// Finnish banks by the leading digits of the account number (BBAN).
+///
+/// Source: Finance Finland, Finnish monetary institution codes and BICs.
+/// The longest matching prefix is used.
+#[rustfmt::skip]
+const FINNISH_BANKS: [FinnishBank; 28] = [
+    FinnishBank { prefix: "1", name: "Nordea Pankki", bic: "NDEAFIHH" },
+    FinnishBank { prefix: "2", name: "Nordea Pankki", bic: "NDEAFIHH" },
+    FinnishBank { prefix: "31", name: "Handelsbanken", bic: "HANDFIHH" },
+    FinnishBank { prefix: "33", name: "Skandinaviska Enskilda Banken", bic: "ESSEFIHX" },
+    FinnishBank { prefix: "34", name: "Danske Bank", bic: "DABAFIHX" },
+    FinnishBank { prefix: "36", name: "S-Pankki", bic: "SBANFIHH" },
+    FinnishBank { prefix: "37", name: "DNB Bank ASA, Finland Branch", bic: "DNBAFIHX" },
+    FinnishBank { prefix: "38", name: "Swedbank", bic: "SWEDFIHH" },
+    FinnishBank { prefix: "39", name: "S-Pankki", bic: "SBANFIHH" },
+    FinnishBank { prefix: "4", name: "Säästöpankit", bic: "ITELFIHH" },
+    FinnishBank { prefix: "405", name: "Aktia Pankki", bic: "HELSFIHH" },
+    FinnishBank { prefix: "470", name: "POP Pankit", bic: "POPFFI22" },
+    FinnishBank { prefix: "471", name: "POP Pankit", bic: "POPFFI22" },
+    FinnishBank { prefix: "472", name: "POP Pankit", bic: "POPFFI22" },
+    FinnishBank { prefix: "473", name: "POP Pankit", bic: "POPFFI22" },
+    FinnishBank { prefix: "474", name: "POP Pankit", bic: "POPFFI22" },
+    FinnishBank { prefix: "475", name: "POP Pankit", bic: "POPFFI22" },
+    FinnishBank { prefix: "476", name: "POP Pankit", bic: "POPFFI22" },
+    FinnishBank { prefix: "477", name: "POP Pankit", bic: "POPFFI22" },
+    FinnishBank { prefix: "478", name: "POP Pankit", bic: "POPFFI22" },
+    FinnishBank { prefix: "497", name: "Aktia Pankki", bic: "HELSFIHH" },
+    FinnishBank { prefix: "5", name: "OP Ryhmä", bic: "OKOYFIHH" },
+    FinnishBank { prefix: "6", name: "Ålandsbanken", bic: "AABAFI22" },
+    FinnishBank { prefix: "713", name: "Citibank", bic: "CITIFIHX" },
+    FinnishBank { prefix: "715", name: "Säästöpankit", bic: "ITELFIHH" },
+    FinnishBank { prefix: "717", name: "Bigbank", bic: "BIGKFIH1" },
+    FinnishBank { prefix: "799", name: "Holvi", bic: "HOLVFIHH" },
+    FinnishBank { prefix: "8", name: "Danske Bank", bic: "DABAFIHH" },
+];
+
+/// Returns the Finnish bank of the account.
+///
+/// Returns None if the IBAN is not Finnish or the bank is not known.
+pub fn finnish_bank(iban: &Iban) -> Option<&'static FinnishBank> {
+    if iban.country_code() != FINLAND {
+        return None;
+    }
+    FINNISH_BANKS
+        .iter()
+        .filter(|bank| iban.bban().starts_with(bank.prefix))
+        .max_by_key(|bank| bank.prefix.len())
+}
@@ -0,0 +1,109 @@
+use super::{normalize, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Branch code used for the primary office of the bank.
+const PRIMARY_OFFICE: &str = "XXX";
+
+/// Business Identifier Code (SWIFT code) of a bank.
+///
+/// BIC consists of four letter bank code, two letter country code, two
+/// character location code and an optional three character branch code.
+/// The value is stored in upper case.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct Bic(String);
+
+/// Implementation of the Bic.
+impl Bic {
+    /// Parses and validates BIC.
+    pub fn parse(value: &str) -> Result<Bic, ValidationError> {
+        let bic = normalize(value);
+        let length = bic.chars().count();
+        if length != 8 && length != 11 {
+            return Err(ValidationError::InvalidLength(length));
+        }
+        for (i, c) in bic.chars().enumerate() {
+            let valid = if i < 6 {
+                c.is_ascii_alphabetic()
+            } else {
+                c.is_ascii_alphanumeric()
+            };
+            if !valid {
+                return Err(ValidationError::InvalidCharacter(c));
+            }
+        }
+        Ok(Bic(bic))
+    }
+
+    /// Returns BIC as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the four letter code of the bank.
+    pub fn bank_code(&self) -> &str {
+        &self.0[0..4]
+    }
+
+    /// Returns ISO 3166-1 alpha-2 code of the country of the bank.
+    pub fn country_code(&self) -> &str {
+        &self.0[4..6]
+    }
+
+    /// Returns the location code.
+    pub fn location_code(&self) -> &str {
+        &self.0[6..8]
+    }
+
+    /// Returns the branch code if present.
+    pub fn branch_code(&self) -> Option<&str> {
+        self.0.get(8..11)
+    }
+
+    /// Returns true if the BIC identifies the primary office of the bank.
+    pub fn is_primary_office(&self) -> bool {
+        matches!(self.branch_code(), None | Some(PRIMARY_OFFICE))
+    }
+
+    /// Returns the eight character BIC of the bank without branch code.
+    pub fn without_branch(&self) -> Bic {
+        Bic(self.0[0..8].to_string())
+    }
+}
+
+impl FromStr for Bic {
+    type Err = ValidationError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Bic::parse(value)
+    }
+}
+
+impl TryFrom<String> for Bic {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Bic::parse(&value)
+    }
+}
+
+impl From<Bic> for String {
+    fn from(bic: Bic) -> Self {
+        bic.0
+    }
+}
+
+impl AsRef<str> for Bic {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Bic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
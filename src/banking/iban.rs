@@ -0,0 +1,202 @@
+use super::{finnish_bank, mod97, normalize, FinnishBank, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Minimum length of an IBAN in any country.
+const MIN_LENGTH: usize = 15;
+
+/// Maximum length of an IBAN in any country.
+const MAX_LENGTH: usize = 34;
+
+/// IBAN format of a country.
+struct CountryFormat {
+    /// ISO 3166-1 alpha-2 country code.
+    country: &'static str,
+    /// Length of the IBAN in electronic format.
+    length: usize,
+    /// Position of the bank code in the BBAN.
+    bank_code_offset: usize,
+    /// Length of the bank code.
+    bank_code_length: usize,
+}
+
+/// Known IBAN formats of the SEPA countries.
+#[rustfmt::skip]
+const COUNTRY_FORMATS: [CountryFormat; 37] = [
+    CountryFormat { country: "AD", length: 24, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "AT", length: 20, bank_code_offset: 0, bank_code_length: 5 },
+    CountryFormat { country: "BE", length: 16, bank_code_offset: 0, bank_code_length: 3 },
+    CountryFormat { country: "BG", length: 22, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "CH", length: 21, bank_code_offset: 0, bank_code_length: 5 },
+    CountryFormat { country: "CY", length: 28, bank_code_offset: 0, bank_code_length: 3 },
+    CountryFormat { country: "CZ", length: 24, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "DE", length: 22, bank_code_offset: 0, bank_code_length: 8 },
+    CountryFormat { country: "DK", length: 18, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "EE", length: 20, bank_code_offset: 0, bank_code_length: 2 },
+    CountryFormat { country: "ES", length: 24, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "FI", length: 18, bank_code_offset: 0, bank_code_length: 3 },
+    CountryFormat { country: "FR", length: 27, bank_code_offset: 0, bank_code_length: 5 },
+    CountryFormat { country: "GB", length: 22, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "GI", length: 23, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "GR", length: 27, bank_code_offset: 0, bank_code_length: 3 },
+    CountryFormat { country: "HR", length: 21, bank_code_offset: 0, bank_code_length: 7 },
+    CountryFormat { country: "HU", length: 28, bank_code_offset: 0, bank_code_length: 3 },
+    CountryFormat { country: "IE", length: 22, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "IS", length: 26, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "IT", length: 27, bank_code_offset: 1, bank_code_length: 5 },
+    CountryFormat { country: "LI", length: 21, bank_code_offset: 0, bank_code_length: 5 },
+    CountryFormat { country: "LT", length: 20, bank_code_offset: 0, bank_code_length: 5 },
+    CountryFormat { country: "LU", length: 20, bank_code_offset: 0, bank_code_length: 3 },
+    CountryFormat { country: "LV", length: 21, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "MC", length: 27, bank_code_offset: 0, bank_code_length: 5 },
+    CountryFormat { country: "MT", length: 31, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "NL", length: 18, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "NO", length: 15, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "PL", length: 28, bank_code_offset: 0, bank_code_length: 8 },
+    CountryFormat { country: "PT", length: 25, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "RO", length: 24, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "SE", length: 24, bank_code_offset: 0, bank_code_length: 3 },
+    CountryFormat { country: "SI", length: 19, bank_code_offset: 0, bank_code_length: 5 },
+    CountryFormat { country: "SK", length: 24, bank_code_offset: 0, bank_code_length: 4 },
+    CountryFormat { country: "SM", length: 27, bank_code_offset: 1, bank_code_length: 5 },
+    CountryFormat { country: "VA", length: 22, bank_code_offset: 0, bank_code_length: 3 },
+];
+
+/// Returns IBAN format of the country if known.
+fn country_format(country: &str) -> Option<&'static CountryFormat> {
+    COUNTRY_FORMATS.iter().find(|f| f.country == country)
+}
+
+/// International Bank Account Number.
+///
+/// IBAN is validated when parsed: the length must match the country and
+/// the check digits must pass the mod-97 check. Spaces are allowed in the
+/// input, and the value is stored in the electronic format.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct Iban(String);
+
+/// Implementation of the Iban.
+impl Iban {
+    /// Parses and validates IBAN in electronic or print format.
+    pub fn parse(value: &str) -> Result<Iban, ValidationError> {
+        let iban = normalize(value);
+        let length = iban.chars().count();
+        if !(MIN_LENGTH..=MAX_LENGTH).contains(&length) {
+            return Err(ValidationError::InvalidLength(length));
+        }
+        if let Some(c) = iban.chars().find(|c| !c.is_ascii_alphanumeric()) {
+            return Err(ValidationError::InvalidCharacter(c));
+        }
+        let country = &iban[0..2];
+        if let Some(c) = country.chars().find(|c| !c.is_ascii_alphabetic()) {
+            return Err(ValidationError::InvalidCharacter(c));
+        }
+        if let Some(c) = iban[2..4].chars().find(|c| !c.is_ascii_digit()) {
+            return Err(ValidationError::InvalidCharacter(c));
+        }
+        if let Some(format) = country_format(country) {
+            if format.length != length {
+                return Err(ValidationError::InvalidLength(length));
+            }
+        }
+        if mod97(&iban) != 1 {
+            return Err(ValidationError::InvalidChecksum);
+        }
+        Ok(Iban(iban))
+    }
+
+    /// Creates IBAN from country code and national account number (BBAN)
+    /// calculating the check digits.
+    pub fn from_bban(country: &str, bban: &str) -> Result<Iban, ValidationError> {
+        let country = normalize(country);
+        if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(ValidationError::InvalidCountry(country));
+        }
+        let bban = normalize(bban);
+        let check = 98 - mod97(&format!("{}00{}", country, bban));
+        Iban::parse(&format!("{}{:02}{}", country, check, bban))
+    }
+
+    /// Returns IBAN in electronic format without spaces.
+    pub fn electronic(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns IBAN in print format with spaces between groups of four
+    /// characters.
+    pub fn print(&self) -> String {
+        let chars: Vec<char> = self.0.chars().collect();
+        chars
+            .chunks(4)
+            .map(|chunk| chunk.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Returns ISO 3166-1 alpha-2 code of the country of the account.
+    pub fn country_code(&self) -> &str {
+        &self.0[0..2]
+    }
+
+    /// Returns the two check digits.
+    pub fn check_digits(&self) -> &str {
+        &self.0[2..4]
+    }
+
+    /// Returns national account number (BBAN) part of the IBAN.
+    pub fn bban(&self) -> &str {
+        &self.0[4..]
+    }
+
+    /// Returns national code of the bank of the account.
+    ///
+    /// The bank code is only known for IBANs of the SEPA countries.
+    pub fn bank_code(&self) -> Option<&str> {
+        let format = country_format(self.country_code())?;
+        let start = format.bank_code_offset;
+        self.bban().get(start..start + format.bank_code_length)
+    }
+
+    /// Returns the bank of the account if it is a Finnish account of a
+    /// known bank.
+    pub fn finnish_bank(&self) -> Option<&'static FinnishBank> {
+        finnish_bank(self)
+    }
+}
+
+impl FromStr for Iban {
+    type Err = ValidationError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Iban::parse(value)
+    }
+}
+
+impl TryFrom<String> for Iban {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Iban::parse(&value)
+    }
+}
+
+impl From<Iban> for String {
+    fn from(iban: Iban) -> Self {
+        iban.0
+    }
+}
+
+impl AsRef<str> for Iban {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Iban {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
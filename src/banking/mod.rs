@@ -0,0 +1,79 @@
+//! This module contains types for identifiers used in Finnish and
+//! European banking, such as IBAN account numbers, BIC codes, payment
+//! reference numbers and transaction codes.
+//!
+//! The types validate their contents when parsed, so a value of the type
+//! is always well-formed. They can be used with the models as well as in
+//! own payment forms.
+
+use std::fmt;
+
+mod banks;
+mod bic;
+mod iban;
+mod op_transaction_code;
+mod reference;
+mod transaction_code;
+
+pub use banks::{finnish_bank, FinnishBank};
+pub use bic::Bic;
+pub use iban::Iban;
+pub use op_transaction_code::{
+    op_transaction_code, transaction_category, OpTransactionCode, TransactionCategory,
+};
+pub use reference::{Reference, ReferenceKind};
+pub use transaction_code::BankTransactionCode;
+
+/// Error in validating a banking identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Identifier has invalid length.
+    InvalidLength(usize),
+    /// Identifier contains a character not allowed in the position.
+    InvalidCharacter(char),
+    /// Country of the identifier is not valid.
+    InvalidCountry(String),
+    /// Check digits of the identifier do not match.
+    InvalidChecksum,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::InvalidLength(length) => write!(f, "Invalid length {}", length),
+            ValidationError::InvalidCharacter(c) => write!(f, "Invalid character '{}'", c),
+            ValidationError::InvalidCountry(country) => {
+                write!(f, "Invalid country code '{}'", country)
+            }
+            ValidationError::InvalidChecksum => write!(f, "Invalid check digits"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Removes whitespace from the value and converts it to upper case.
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Calculates remainder of the ISO 7064 MOD 97-10 check used by IBAN and
+/// RF references.
+///
+/// The four first characters are moved to the end and letters are
+/// converted to numbers A = 10, B = 11, ..., Z = 35.
+fn mod97(value: &str) -> u32 {
+    let (head, tail) = value.split_at(4);
+    tail.chars().chain(head.chars()).fold(0, |rem, c| {
+        let digit = c.to_digit(36).unwrap_or(0);
+        if digit < 10 {
+            (rem * 10 + digit) % 97
+        } else {
+            (rem * 100 + digit) % 97
+        }
+    })
+}
@@ -0,0 +1,127 @@
+use super::BankTransactionCode;
+use std::fmt;
+
+/// Coarse category of a transaction for reporting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransactionCategory {
+    /// Salary or other payroll payment.
+    Salary,
+    /// Purchase paid with a payment card.
+    CardPurchase,
+    /// E-invoice or other bill paid with the bill payment service.
+    EInvoice,
+    /// Cash withdrawal from an ATM.
+    AtmWithdrawal,
+    /// Interest paid or received.
+    Interest,
+    /// Fee charged by the bank.
+    Fee,
+    /// Deposit to the account.
+    Deposit,
+    /// Withdrawal from the account.
+    Withdrawal,
+    /// Direct debit.
+    DirectDebit,
+    /// Credit transfer, such as a reference or recurring payment.
+    Transfer,
+    /// Payment related to a loan.
+    Loan,
+    /// Cheque.
+    Cheque,
+    /// Transaction not belonging to other categories.
+    Other,
+}
+
+impl fmt::Display for TransactionCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TransactionCategory::Salary => "Salary",
+            TransactionCategory::CardPurchase => "Card purchase",
+            TransactionCategory::EInvoice => "E-invoice",
+            TransactionCategory::AtmWithdrawal => "ATM withdrawal",
+            TransactionCategory::Interest => "Interest",
+            TransactionCategory::Fee => "Fee",
+            TransactionCategory::Deposit => "Deposit",
+            TransactionCategory::Withdrawal => "Withdrawal",
+            TransactionCategory::DirectDebit => "Direct debit",
+            TransactionCategory::Transfer => "Transfer",
+            TransactionCategory::Loan => "Loan",
+            TransactionCategory::Cheque => "Cheque",
+            TransactionCategory::Other => "Other",
+        };
+        f.write_str(name)
+    }
+}
+
+/// OP transaction code (tapahtumalaji) with descriptions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpTransactionCode {
+    /// Three digit transaction code.
+    pub code: &'static str,
+    /// Description in Finnish.
+    pub finnish: &'static str,
+    /// Description in English.
+    pub english: &'static str,
+    /// Category of the transactions with the code.
+    pub category: TransactionCategory,
+}
+
+/// Transaction codes of Finnish account statements.
+///
+/// Source: Finance Finland, account statement transaction types.
+#[rustfmt::skip]
+const OP_TRANSACTION_CODES: [OpTransactionCode; 18] = [
+    OpTransactionCode { code: "700", finnish: "Maksuliikennepalvelu", english: "Payment transaction service", category: TransactionCategory::Transfer },
+    OpTransactionCode { code: "701", finnish: "Toistuva maksuliikennepalvelu", english: "Recurring payment service", category: TransactionCategory::Transfer },
+    OpTransactionCode { code: "702", finnish: "Laskujen maksupalvelu", english: "Bill payment service", category: TransactionCategory::EInvoice },
+    OpTransactionCode { code: "703", finnish: "Maksupäätepalvelu", english: "Payment terminal service", category: TransactionCategory::CardPurchase },
+    OpTransactionCode { code: "704", finnish: "Suoramaksupalvelu", english: "Direct debit service", category: TransactionCategory::DirectDebit },
+    OpTransactionCode { code: "705", finnish: "Viitesiirto", english: "Reference payment", category: TransactionCategory::Transfer },
+    OpTransactionCode { code: "706", finnish: "Maksupalvelu", english: "Payment service", category: TransactionCategory::Transfer },
+    OpTransactionCode { code: "710", finnish: "Talletus", english: "Deposit", category: TransactionCategory::Deposit },
+    OpTransactionCode { code: "720", finnish: "Nosto", english: "Withdrawal", category: TransactionCategory::Withdrawal },
+    OpTransactionCode { code: "721", finnish: "Maksukorttimaksu", english: "Card payment", category: TransactionCategory::CardPurchase },
+    OpTransactionCode { code: "722", finnish: "Shekki", english: "Cheque", category: TransactionCategory::Cheque },
+    OpTransactionCode { code: "730", finnish: "Pankkimaksu", english: "Bank fee", category: TransactionCategory::Fee },
+    OpTransactionCode { code: "740", finnish: "Korkomaksu", english: "Interest charge", category: TransactionCategory::Interest },
+    OpTransactionCode { code: "750", finnish: "Hyvityskorko", english: "Interest credit", category: TransactionCategory::Interest },
+    OpTransactionCode { code: "760", finnish: "Laina", english: "Loan", category: TransactionCategory::Loan },
+    OpTransactionCode { code: "761", finnish: "Lainan maksu", english: "Loan repayment", category: TransactionCategory::Loan },
+    OpTransactionCode { code: "770", finnish: "Suoraveloitus", english: "Direct debit", category: TransactionCategory::DirectDebit },
+    OpTransactionCode { code: "780", finnish: "Nollaussiirto", english: "Zero balancing transfer", category: TransactionCategory::Transfer },
+];
+
+/// Returns the OP transaction code from the catalog.
+///
+/// Returns None if the code is not known.
+pub fn op_transaction_code(code: &str) -> Option<&'static OpTransactionCode> {
+    let code = code.trim();
+    OP_TRANSACTION_CODES.iter().find(|c| c.code == code)
+}
+
+/// Returns category of a transaction based on its codes.
+///
+/// ISO 20022 code is used to recognize salaries, card purchases and ATM
+/// withdrawals which the OP transaction codes do not separate. Otherwise
+/// the category of the OP transaction code is used.
+pub fn transaction_category(
+    op_code: Option<&str>,
+    iso_code: Option<&BankTransactionCode>,
+) -> TransactionCategory {
+    if let Some(iso_code) = iso_code {
+        match iso_code.sub_family() {
+            "SALA" => return TransactionCategory::Salary,
+            "CWDL" | "XBCW" => return TransactionCategory::AtmWithdrawal,
+            "POSD" | "POSC" | "POSP" | "SMRT" => return TransactionCategory::CardPurchase,
+            "INTR" => return TransactionCategory::Interest,
+            _ => {}
+        }
+        if iso_code.is_card_transaction() {
+            return TransactionCategory::CardPurchase;
+        }
+    }
+    op_code
+        .and_then(op_transaction_code)
+        .map(|code| code.category)
+        .unwrap_or(TransactionCategory::Other)
+}
@@ -0,0 +1,223 @@
+use super::{mod97, normalize, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Prefix of the RF creditor references.
+const RF_PREFIX: &str = "RF";
+
+/// Minimum length of a national reference including the check digit.
+const NATIONAL_MIN_LENGTH: usize = 4;
+
+/// Maximum length of a national reference including the check digit.
+const NATIONAL_MAX_LENGTH: usize = 20;
+
+/// Maximum length of the RF reference including the prefix and check
+/// digits.
+const RF_MAX_LENGTH: usize = 25;
+
+/// Weights of the national reference check digit from right to left.
+const NATIONAL_WEIGHTS: [u32; 3] = [7, 3, 1];
+
+/// Kind of the payment reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReferenceKind {
+    /// Finnish national reference with 7-3-1 check digit.
+    National,
+    /// ISO 11649 RF creditor reference.
+    Rf,
+}
+
+/// Payment reference number used to match payments to invoices.
+///
+/// Either a Finnish national reference number or an international ISO
+/// 11649 RF creditor reference. The check digits are validated when
+/// parsed, spaces are allowed in the input and the value is stored in
+/// the electronic format.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct Reference(String);
+
+/// Implementation of the Reference.
+impl Reference {
+    /// Parses and validates national or RF reference.
+    ///
+    /// Leading zeros of national references are ignored.
+    pub fn parse(value: &str) -> Result<Reference, ValidationError> {
+        let reference = normalize(value);
+        if reference.starts_with(RF_PREFIX) {
+            parse_rf(reference)
+        } else {
+            parse_national(reference)
+        }
+    }
+
+    /// Creates national reference from the base number by calculating
+    /// the check digit.
+    pub fn national(base: &str) -> Result<Reference, ValidationError> {
+        let base = digits(&normalize(base))?;
+        let check = national_check_digit(&base);
+        parse_national(format!("{}{}", base, check))
+    }
+
+    /// Creates RF reference from the alphanumeric base by calculating the
+    /// check digits.
+    pub fn rf(base: &str) -> Result<Reference, ValidationError> {
+        let base = normalize(base);
+        if let Some(c) = base.chars().find(|c| !c.is_ascii_alphanumeric()) {
+            return Err(ValidationError::InvalidCharacter(c));
+        }
+        let check = 98 - mod97(&format!("{}00{}", RF_PREFIX, base));
+        parse_rf(format!("{}{:02}{}", RF_PREFIX, check, base))
+    }
+
+    /// Returns kind of the reference.
+    pub fn kind(&self) -> ReferenceKind {
+        if self.0.starts_with(RF_PREFIX) {
+            ReferenceKind::Rf
+        } else {
+            ReferenceKind::National
+        }
+    }
+
+    /// Returns reference in electronic format without spaces.
+    pub fn electronic(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns reference in print format.
+    ///
+    /// National references are grouped by five digits from the right and
+    /// RF references by four characters from the left.
+    pub fn print(&self) -> String {
+        let chars: Vec<char> = self.0.chars().collect();
+        match self.kind() {
+            ReferenceKind::Rf => group(chars.chunks(4)),
+            ReferenceKind::National => {
+                let mut groups: Vec<String> = chars
+                    .rchunks(5)
+                    .map(|chunk| chunk.iter().collect())
+                    .collect();
+                groups.reverse();
+                groups.join(" ")
+            }
+        }
+    }
+
+    /// Returns the reference as RF reference.
+    ///
+    /// National references are converted by adding the RF prefix and check
+    /// digits.
+    pub fn to_rf(&self) -> Reference {
+        match self.kind() {
+            ReferenceKind::Rf => self.clone(),
+            ReferenceKind::National => Reference::rf(&self.0).expect("National reference fits RF"),
+        }
+    }
+
+    /// Returns the reference as national reference.
+    ///
+    /// Returns None for RF references which do not contain a valid
+    /// national reference.
+    pub fn to_national(&self) -> Option<Reference> {
+        match self.kind() {
+            ReferenceKind::National => Some(self.clone()),
+            ReferenceKind::Rf => parse_national(self.0[4..].to_string()).ok(),
+        }
+    }
+}
+
+/// Joins chunks of characters with spaces.
+fn group<'a, I: Iterator<Item = &'a [char]>>(chunks: I) -> String {
+    chunks
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Returns the value if it only contains digits.
+fn digits(value: &str) -> Result<String, ValidationError> {
+    match value.chars().find(|c| !c.is_ascii_digit()) {
+        Some(c) => Err(ValidationError::InvalidCharacter(c)),
+        None => Ok(value.to_string()),
+    }
+}
+
+/// Calculates check digit of the national reference base.
+fn national_check_digit(base: &str) -> u32 {
+    let sum: u32 = base
+        .chars()
+        .rev()
+        .zip(NATIONAL_WEIGHTS.iter().cycle())
+        .map(|(c, weight)| c.to_digit(10).unwrap_or(0) * weight)
+        .sum();
+    (10 - sum % 10) % 10
+}
+
+/// Validates national reference in normalized form.
+fn parse_national(reference: String) -> Result<Reference, ValidationError> {
+    let reference = digits(&reference)?;
+    let reference = reference.trim_start_matches('0');
+    let length = reference.len();
+    if !(NATIONAL_MIN_LENGTH..=NATIONAL_MAX_LENGTH).contains(&length) {
+        return Err(ValidationError::InvalidLength(length));
+    }
+    let (base, check) = reference.split_at(length - 1);
+    if check.parse::<u32>().ok() != Some(national_check_digit(base)) {
+        return Err(ValidationError::InvalidChecksum);
+    }
+    Ok(Reference(reference.to_string()))
+}
+
+/// Validates RF reference in normalized form.
+fn parse_rf(reference: String) -> Result<Reference, ValidationError> {
+    let length = reference.chars().count();
+    if !(RF_PREFIX.len() + 3..=RF_MAX_LENGTH).contains(&length) {
+        return Err(ValidationError::InvalidLength(length));
+    }
+    if let Some(c) = reference.chars().find(|c| !c.is_ascii_alphanumeric()) {
+        return Err(ValidationError::InvalidCharacter(c));
+    }
+    if let Some(c) = reference[2..4].chars().find(|c| !c.is_ascii_digit()) {
+        return Err(ValidationError::InvalidCharacter(c));
+    }
+    if mod97(&reference) != 1 {
+        return Err(ValidationError::InvalidChecksum);
+    }
+    Ok(Reference(reference))
+}
+
+impl FromStr for Reference {
+    type Err = ValidationError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Reference::parse(value)
+    }
+}
+
+impl TryFrom<String> for Reference {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Reference::parse(&value)
+    }
+}
+
+impl From<Reference> for String {
+    fn from(reference: Reference) -> Self {
+        reference.0
+    }
+}
+
+impl AsRef<str> for Reference {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
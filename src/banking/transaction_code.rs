@@ -0,0 +1,285 @@
+use super::{normalize, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Separator between the code parts.
+const SEPARATOR: char = '-';
+
+/// Length of each part of the code.
+const PART_LENGTH: usize = 4;
+
+/// Domain codes of the ISO 20022 external code list.
+const DOMAINS: [(&str, &str); 11] = [
+    ("ACMT", "Account Management"),
+    ("CAMT", "Cash Management"),
+    ("CMDT", "Commodities"),
+    ("DERV", "Derivatives"),
+    ("FORX", "Foreign Exchange"),
+    ("LDAS", "Loans, Deposits & Syndications"),
+    ("PMET", "Precious Metal"),
+    ("PMNT", "Payments"),
+    ("SECU", "Securities"),
+    ("TRAD", "Trade Services"),
+    ("XTND", "Extended Domain"),
+];
+
+/// Family codes of the ISO 20022 external code list.
+const FAMILIES: [(&str, &str); 40] = [
+    ("ACCB", "Account Balancing"),
+    ("ACOP", "Additional Miscellaneous Credit Operations"),
+    ("ADOP", "Additional Miscellaneous Debit Operations"),
+    ("BLOC", "Blocked Transactions"),
+    ("CAPL", "Cash Pooling"),
+    ("CASH", "Miscellaneous Securities Operations"),
+    ("CCRD", "Customer Card Transactions"),
+    ("CNTR", "Counter Transactions"),
+    ("COLL", "Collateral Management"),
+    ("CORP", "Corporate Action"),
+    ("CSLN", "Consumer Loans"),
+    ("CUST", "Custody"),
+    ("DCCT", "Documentary Credit"),
+    ("DOCC", "Documentary Collection"),
+    ("DRFT", "Drafts/BillOfOrders"),
+    ("FTDP", "Fixed Term Deposits"),
+    ("FTLN", "Fixed Term Loans"),
+    ("GUAR", "Guarantees"),
+    ("ICCN", "Issued Cash Concentration Transactions"),
+    ("ICDT", "Issued Credit Transfers"),
+    ("ICHQ", "Issued Cheques"),
+    ("IDDT", "Issued Direct Debits"),
+    ("IRCT", "Issued Real-Time Credit Transfers"),
+    ("LBOX", "Lockbox Transactions"),
+    ("MCOP", "Miscellaneous Credit Operations"),
+    ("MCRD", "Merchant Card Transactions"),
+    ("MDOP", "Miscellaneous Debit Operations"),
+    ("MGLN", "Mortgage Loans"),
+    ("NTAV", "Not Available"),
+    ("NTDP", "Notice Deposits"),
+    ("NTLN", "Notice Loans"),
+    ("OPCL", "Opening & Closing"),
+    ("OTHR", "Other"),
+    ("RCCN", "Received Cash Concentration Transactions"),
+    ("RCDT", "Received Credit Transfers"),
+    ("RCHQ", "Received Cheques"),
+    ("RDDT", "Received Direct Debits"),
+    ("RRCT", "Received Real-Time Credit Transfers"),
+    ("SETT", "Trade, Clearing and Settlement"),
+    ("SYDN", "Syndications"),
+];
+
+/// Sub-family codes of the ISO 20022 external code list.
+const SUB_FAMILIES: [(&str, &str); 57] = [
+    ("ACCC", "Account Closing"),
+    ("ACCO", "Account Opening"),
+    ("ADJT", "Adjustments"),
+    ("ARET", "ACH Return"),
+    ("ATXN", "ACH Transaction"),
+    ("AUTT", "Automatic Transfer"),
+    ("BBDD", "SEPA B2B Direct Debit"),
+    ("BCDP", "Branch Deposit"),
+    ("BCWD", "Branch Withdrawal"),
+    ("BOOK", "Internal Book Transfer"),
+    ("CAJT", "Credit Adjustments"),
+    ("CCHQ", "Cheque"),
+    ("CDPT", "Cash Deposit"),
+    ("CHRG", "Charges"),
+    ("COMM", "Commission"),
+    ("CQRV", "Cheque Reversal"),
+    ("CWDL", "Cash Withdrawal"),
+    ("DAJT", "Debit Adjustments"),
+    ("DDWN", "Drawdown"),
+    ("DMCT", "Domestic Credit Transfer"),
+    ("ESCT", "SEPA Credit Transfer"),
+    ("ESDD", "SEPA Core Direct Debit"),
+    ("FCDP", "Foreign Currency Deposit"),
+    ("FCWD", "Foreign Currency Withdrawal"),
+    ("FEES", "Fees"),
+    ("FICT", "Financial Institution Credit Transfer"),
+    ("INTR", "Interests"),
+    ("NTAV", "Not Available"),
+    ("ODFT", "Overdraft"),
+    ("OODD", "One-Off Direct Debit"),
+    ("OTHR", "Other"),
+    ("PMDD", "Direct Debit Payment"),
+    ("POSC", "Credit Card Payment"),
+    ("POSD", "Point-of-Sale Payment - Debit Card"),
+    ("POSP", "Point-of-Sale Payment"),
+    ("PPAY", "Principal Payment"),
+    ("PRCT", "Priority Credit Transfer"),
+    ("RCDD", "Reversal due to Payment Cancellation Request"),
+    ("RIMB", "Reimbursement"),
+    ("RNEW", "Renewal"),
+    ("RPCR", "Reversal due to Payment Cancellation Request"),
+    ("RRTN", "Reversal due to Payment Return"),
+    ("SALA", "Payroll/Salary Payment"),
+    ("SDVA", "Same Day Value Credit Transfer"),
+    ("SMRT", "Smart-Card Payment"),
+    ("STAM", "Settlement at Maturity"),
+    ("STDO", "Standing Order"),
+    ("SWEP", "Sweeping"),
+    ("TAXE", "Taxes"),
+    ("TOPG", "Topping"),
+    ("UPCQ", "Unpaid Cheque"),
+    ("UPDD", "Reversal due to Return/Unpaid Direct Debit"),
+    ("URCQ", "Cheque Under Reserve"),
+    ("VCOM", "Credit Transfer with Agreed Commercial Information"),
+    ("XBCT", "Cross-Border Credit Transfer"),
+    ("XBCW", "Cross-Border Cash Withdrawal"),
+    ("ZABA", "Zero Balancing"),
+];
+
+/// Families of credit transfers.
+const CREDIT_TRANSFER_FAMILIES: [&str; 4] = ["ICDT", "RCDT", "IRCT", "RRCT"];
+
+/// Families of direct debits.
+const DIRECT_DEBIT_FAMILIES: [&str; 2] = ["IDDT", "RDDT"];
+
+/// Families of card transactions.
+const CARD_FAMILIES: [&str; 2] = ["CCRD", "MCRD"];
+
+/// Sub-families of SEPA payments.
+const SEPA_SUB_FAMILIES: [&str; 3] = ["ESCT", "ESDD", "BBDD"];
+
+/// Returns description of the code from the table.
+fn describe(table: &[(&str, &'static str)], code: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, description)| *description)
+}
+
+/// ISO 20022 bank transaction code, for example PMNT-ICDT-ESCT.
+///
+/// The code consists of domain, family and sub-family codes. Codes not
+/// included in the embedded code list are accepted but have no
+/// descriptions.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct BankTransactionCode {
+    domain: String,
+    family: String,
+    sub_family: String,
+}
+
+/// Implementation of the BankTransactionCode.
+impl BankTransactionCode {
+    /// Parses code in format DOMAIN-FAMILY-SUBFAMILY.
+    pub fn parse(value: &str) -> Result<BankTransactionCode, ValidationError> {
+        let code = normalize(value);
+        let parts: Vec<&str> = code.split(SEPARATOR).collect();
+        if parts.len() != 3 || parts.iter().any(|p| p.len() != PART_LENGTH) {
+            return Err(ValidationError::InvalidLength(code.chars().count()));
+        }
+        let invalid = parts
+            .iter()
+            .flat_map(|p| p.chars())
+            .find(|c| !c.is_ascii_alphanumeric());
+        if let Some(c) = invalid {
+            return Err(ValidationError::InvalidCharacter(c));
+        }
+        Ok(BankTransactionCode {
+            domain: parts[0].to_string(),
+            family: parts[1].to_string(),
+            sub_family: parts[2].to_string(),
+        })
+    }
+
+    /// Returns the domain code, for example PMNT.
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Returns the family code, for example ICDT.
+    pub fn family(&self) -> &str {
+        &self.family
+    }
+
+    /// Returns the sub-family code, for example ESCT.
+    pub fn sub_family(&self) -> &str {
+        &self.sub_family
+    }
+
+    /// Returns description of the domain, for example "Payments".
+    pub fn domain_description(&self) -> Option<&'static str> {
+        describe(&DOMAINS, &self.domain)
+    }
+
+    /// Returns description of the family, for example "Issued Credit
+    /// Transfers".
+    pub fn family_description(&self) -> Option<&'static str> {
+        describe(&FAMILIES, &self.family)
+    }
+
+    /// Returns description of the sub-family, for example "SEPA Credit
+    /// Transfer".
+    pub fn sub_family_description(&self) -> Option<&'static str> {
+        describe(&SUB_FAMILIES, &self.sub_family)
+    }
+
+    /// Returns descriptions of all parts separated with slashes, for
+    /// example "Payments / Issued Credit Transfers / SEPA Credit Transfer".
+    ///
+    /// Unknown parts are described with their codes.
+    pub fn description(&self) -> String {
+        format!(
+            "{} / {} / {}",
+            self.domain_description().unwrap_or(&self.domain),
+            self.family_description().unwrap_or(&self.family),
+            self.sub_family_description().unwrap_or(&self.sub_family)
+        )
+    }
+
+    /// Returns true if the code is a credit transfer.
+    pub fn is_credit_transfer(&self) -> bool {
+        CREDIT_TRANSFER_FAMILIES.contains(&self.family.as_str())
+    }
+
+    /// Returns true if the code is a direct debit.
+    pub fn is_direct_debit(&self) -> bool {
+        DIRECT_DEBIT_FAMILIES.contains(&self.family.as_str())
+    }
+
+    /// Returns true if the code is a card transaction.
+    pub fn is_card_transaction(&self) -> bool {
+        CARD_FAMILIES.contains(&self.family.as_str())
+    }
+
+    /// Returns true if the code is a SEPA credit transfer or direct debit.
+    pub fn is_sepa(&self) -> bool {
+        SEPA_SUB_FAMILIES.contains(&self.sub_family.as_str())
+    }
+}
+
+impl FromStr for BankTransactionCode {
+    type Err = ValidationError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        BankTransactionCode::parse(value)
+    }
+}
+
+impl TryFrom<String> for BankTransactionCode {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        BankTransactionCode::parse(&value)
+    }
+}
+
+impl From<BankTransactionCode> for String {
+    fn from(code: BankTransactionCode) -> Self {
+        code.to_string()
+    }
+}
+
+impl fmt::Display for BankTransactionCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}{}",
+            self.domain, SEPARATOR, self.family, SEPARATOR, self.sub_family
+        )
+    }
+}
@@ -4,11 +4,14 @@
 use crate::apis::accounts::AccountsApi;
 use crate::apis::funds::FundsApi;
 use crate::apis::holdings::HoldingsApi;
+use crate::error::Result;
 use crate::model::accounts::*;
 use crate::model::funds::*;
 use crate::model::holdings::HoldingsInformation;
 use crate::options::Options;
-use std::error::Error;
+use crate::transport::Transport;
+use chrono::NaiveDateTime;
+use futures::stream::Stream;
 use std::sync::Arc;
 
 pub struct Client {
@@ -32,6 +35,15 @@ impl Client {
         }
     }
 
+    /// Creates new client that sends all requests with the given transport.
+    ///
+    /// The transport is set to the options so it is also used by other
+    /// clients sharing the same options.
+    pub fn with_transport(options: Arc<Options>, transport: Arc<dyn Transport>) -> Client {
+        options.set_transport(transport);
+        Client::new(options)
+    }
+
     /// Returns options used by this client.
     ///
     /// Note that the return value is a clone of the options inside
@@ -44,22 +56,22 @@ impl Client {
     }
 
     /// Gets all accounts from the API and returns list of them.
-    pub async fn accounts(&self) -> Result<AccountList, Box<dyn Error>> {
+    pub async fn accounts(&self) -> Result<AccountList> {
         self.accounts_api.accounts().await
     }
 
     /// Gets single account from the API based on accountId.
-    pub async fn account(&self, account_id: String) -> Result<Account, Box<dyn Error>> {
+    pub async fn account(&self, account_id: String) -> Result<Account> {
         self.accounts_api.account(account_id).await
     }
 
     /// Gets all funds from the API and returns of them.
-    pub async fn funds(&self) -> Result<Funds, Box<dyn Error>> {
+    pub async fn funds(&self) -> Result<Funds> {
         self.funds_api.funds().await
     }
 
     /// Gets holdings information from the API and returns it.
-    pub async fn holdings(&self) -> Result<HoldingsInformation, Box<dyn Error>> {
+    pub async fn holdings(&self) -> Result<HoldingsInformation> {
         self.holdings_api.holdings().await
     }
 
@@ -69,7 +81,40 @@ impl Client {
         &self,
         account_id: String,
         params: Option<TransactionParams>,
-    ) -> Result<TransactionList, Box<dyn Error>> {
+    ) -> Result<TransactionList> {
         self.accounts_api.transactions(account_id, params).await
     }
+
+    /// Returns stream of all transactions for a single account with
+    /// account id with optional parameters for filtering the results.
+    ///
+    /// Pages are fetched lazily when the stream is polled and fetching
+    /// stops when there are no more pages or the limits are reached.
+    pub fn transactions_stream(
+        &self,
+        account_id: String,
+        params: Option<TransactionParams>,
+        limits: PagingLimits,
+    ) -> impl Stream<Item = Result<Transaction>> + '_ {
+        self.accounts_api
+            .transactions_stream(account_id, params, limits)
+    }
+
+    /// Gets all transactions booked between local date-times from and to
+    /// for a single account with account id.
+    ///
+    /// Long ranges are split into windows which are fetched page by page.
+    /// Duplicates at the window edges are removed and the transactions
+    /// are returned in booking order.
+    pub async fn transaction_history(
+        &self,
+        account_id: String,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        options: HistoryOptions,
+    ) -> Result<Vec<Transaction>> {
+        self.accounts_api
+            .transaction_history(account_id, from, to, options)
+            .await
+    }
 }
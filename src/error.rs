@@ -0,0 +1,254 @@
+//! This module contains errors returned by the clients for
+//! [OP API](https://op-developer.fi).
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+
+/// Result type returned by all API functions.
+pub type Result<T> = std::result::Result<T, SdkError>;
+
+/// Single error from OP API.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ApiError {
+    pub id: String,
+    pub level: String,
+    pub r#type: String,
+    pub message: String,
+}
+
+/// Container for API errors from OP API.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ApiErrors {
+    pub errors: Vec<ApiError>,
+}
+
+/// Implement functionality to display ApiErrors.
+impl fmt::Display for ApiErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "API error occurred, reasons: {:?}", self.errors)
+    }
+}
+
+/// Implement std::error::Error for ApiErrors.
+impl Error for ApiErrors {}
+
+/// Unsuccessful response from OP API.
+///
+/// Error bodies are parsed on best-effort basis. Status, headers and
+/// the raw body are always kept even if the body is not valid ApiErrors
+/// JSON, for example an HTML error page from a gateway or an empty body.
+#[derive(Debug, Clone)]
+pub struct ErrorResponse {
+    /// HTTP status of the response.
+    pub status: StatusCode,
+    /// HTTP headers of the response.
+    pub headers: HeaderMap,
+    /// Raw body of the response.
+    pub body: String,
+    /// Errors parsed from the body if it contained ApiErrors.
+    pub errors: Option<ApiErrors>,
+}
+
+/// Implementation of the ErrorResponse.
+impl ErrorResponse {
+    /// Creates new ErrorResponse and tries to parse ApiErrors from the body.
+    pub(crate) fn new(status: StatusCode, headers: HeaderMap, body: String) -> ErrorResponse {
+        let errors = serde_json::from_str(&body).ok();
+        ErrorResponse {
+            status,
+            headers,
+            body,
+            errors,
+        }
+    }
+}
+
+/// Maximum number of characters of the raw body shown in error messages.
+const MAX_DISPLAYED_BODY: usize = 200;
+
+/// Implement functionality to display ErrorResponse.
+impl fmt::Display for ErrorResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.errors {
+            Some(errors) => write!(f, "HTTP {}: {}", self.status, errors),
+            None if self.body.trim().is_empty() => write!(f, "HTTP {}", self.status),
+            None => {
+                let body: String = self.body.chars().take(MAX_DISPLAYED_BODY).collect();
+                write!(f, "HTTP {}: {}", self.status, body)
+            }
+        }
+    }
+}
+
+/// Kind of the transport error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportErrorKind {
+    /// Connection to the server could not be established.
+    Connect,
+    /// Request timed out.
+    Timeout,
+    /// Any other failure in sending the request or receiving the response.
+    Other,
+}
+
+/// Error from the transport sending the requests.
+#[derive(Debug)]
+pub struct TransportError {
+    kind: TransportErrorKind,
+    source: Box<dyn Error + Send + Sync>,
+}
+
+/// Implementation of the TransportError.
+impl TransportError {
+    /// Creates new transport error of given kind.
+    pub fn new<E>(kind: TransportErrorKind, source: E) -> TransportError
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        TransportError {
+            kind,
+            source: source.into(),
+        }
+    }
+
+    /// Returns kind of the error.
+    pub fn kind(&self) -> TransportErrorKind {
+        self.kind
+    }
+
+    /// Returns true if the connection could not be established.
+    pub fn is_connect(&self) -> bool {
+        self.kind == TransportErrorKind::Connect
+    }
+
+    /// Returns true if the request timed out.
+    pub fn is_timeout(&self) -> bool {
+        self.kind == TransportErrorKind::Timeout
+    }
+}
+
+/// Implement functionality to display TransportError.
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// Implement std::error::Error for TransportError.
+impl Error for TransportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+impl From<reqwest::Error> for TransportError {
+    fn from(error: reqwest::Error) -> Self {
+        let kind = if error.is_timeout() {
+            TransportErrorKind::Timeout
+        } else if error.is_connect() {
+            TransportErrorKind::Connect
+        } else {
+            TransportErrorKind::Other
+        };
+        TransportError::new(kind, error)
+    }
+}
+
+/// Error returned by the API clients.
+///
+/// The error is both Send and Sync so it can be passed between tasks
+/// and threads.
+#[derive(Debug)]
+pub enum SdkError {
+    /// Sending the request or receiving the response failed.
+    Transport(TransportError),
+    /// API responded with an unsuccessful HTTP status.
+    Http(Box<ErrorResponse>),
+    /// API rejected the credentials (HTTP 401). Either the API key or
+    /// the authorization token is invalid or expired.
+    Authentication(Box<ErrorResponse>),
+    /// User has not given consent to access the resource (HTTP 403).
+    Consent(Box<ErrorResponse>),
+    /// Response body could not be decoded. Contains the raw body, which
+    /// is truncated only when the error is displayed.
+    Decode {
+        source: serde_json::Error,
+        body: String,
+    },
+    /// Options given to the client are invalid.
+    Configuration(String),
+}
+
+/// Implementation of the SdkError.
+impl SdkError {
+    /// Creates error for the unsuccessful response based on its status.
+    pub(crate) fn from_response(response: ErrorResponse) -> SdkError {
+        let response = Box::new(response);
+        match response.status {
+            StatusCode::UNAUTHORIZED => SdkError::Authentication(response),
+            StatusCode::FORBIDDEN => SdkError::Consent(response),
+            _ => SdkError::Http(response),
+        }
+    }
+
+    /// Returns the unsuccessful response if the API responded.
+    pub fn response(&self) -> Option<&ErrorResponse> {
+        match self {
+            SdkError::Http(response)
+            | SdkError::Authentication(response)
+            | SdkError::Consent(response) => Some(response.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Returns HTTP status of the response if the API responded.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.response().map(|r| r.status)
+    }
+
+    /// Returns errors reported by the API if any.
+    pub fn api_errors(&self) -> Option<&ApiErrors> {
+        self.response().and_then(|r| r.errors.as_ref())
+    }
+}
+
+/// Implement functionality to display SdkError.
+impl fmt::Display for SdkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SdkError::Transport(e) => write!(f, "Request failed: {}", e),
+            SdkError::Http(response) => write!(f, "{}", response),
+            SdkError::Authentication(response) => {
+                write!(f, "Authentication failed, {}", response)
+            }
+            SdkError::Consent(response) => write!(f, "Consent missing, {}", response),
+            SdkError::Decode { source, body } => {
+                let body: String = body.chars().take(MAX_DISPLAYED_BODY).collect();
+                write!(f, "Failed to decode response: {}, body: {}", source, body)
+            }
+            SdkError::Configuration(message) => write!(f, "Invalid configuration: {}", message),
+        }
+    }
+}
+
+/// Implement std::error::Error for SdkError.
+impl Error for SdkError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SdkError::Transport(e) => Some(e),
+            SdkError::Decode { source, .. } => Some(source),
+            _ => self
+                .api_errors()
+                .map(|errors| errors as &(dyn Error + 'static)),
+        }
+    }
+}
+
+impl From<TransportError> for SdkError {
+    fn from(error: TransportError) -> Self {
+        SdkError::Transport(error)
+    }
+}
@@ -0,0 +1,371 @@
+//! Export of account statements as ISO 20022 camt.053 XML.
+//!
+//! The document follows the camt.053.001.02 BankToCustomerStatement
+//! schema used by Finnish banks. Opening and closing balances are
+//! derived from the account balances of the transactions.
+
+use super::xml::XmlWriter;
+use super::{local_date, signed_amount, truncate, ExportError, Locale, Result, Statement};
+use crate::banking::ReferenceKind;
+use crate::model::accounts::{Account, Transaction, TransactionParty};
+use crate::model::money::Amount;
+use crate::model::time;
+use chrono::NaiveDate;
+use std::io::Write;
+
+/// Namespace of the camt.053 document.
+const NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:camt.053.001.02";
+
+/// Maximum length of the identifiers and references.
+const MAX_ID_LENGTH: usize = 35;
+
+/// Maximum length of the other account identifiers.
+const MAX_ACCOUNT_ID_LENGTH: usize = 34;
+
+/// Maximum length of the account name.
+const MAX_ACCOUNT_NAME_LENGTH: usize = 70;
+
+/// Maximum length of the party names.
+const MAX_NAME_LENGTH: usize = 140;
+
+/// Proprietary bank transaction code of entries without codes.
+const NOT_AVAILABLE: &str = "NTAV";
+
+/// Maximum length of the unstructured remittance information.
+const MAX_MESSAGE_LENGTH: usize = 140;
+
+/// Format of the date-times in the document.
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Returns credit/debit indicator code of the signed amount.
+fn indicator(amount: Amount) -> &'static str {
+    if amount < Amount::default() {
+        "DBIT"
+    } else {
+        "CRDT"
+    }
+}
+
+/// Formats absolute value of the amount with two decimals.
+fn format_abs(amount: Amount) -> String {
+    Locale::International.format_amount(amount.abs())
+}
+
+/// Writes balance of the statement.
+fn write_balance<W: Write>(
+    xml: &mut XmlWriter<W>,
+    code: &str,
+    amount: Amount,
+    currency: &str,
+    date: NaiveDate,
+) -> Result<()> {
+    xml.start("Bal")?;
+    xml.start("Tp")?;
+    xml.start("CdOrPrtry")?;
+    xml.text("Cd", code)?;
+    xml.end()?;
+    xml.end()?;
+    xml.text_with("Amt", &[("Ccy", currency)], &format_abs(amount))?;
+    xml.text("CdtDbtInd", indicator(amount))?;
+    xml.start("Dt")?;
+    xml.text("Dt", &date.to_string())?;
+    xml.end()?;
+    xml.end()?;
+    Ok(())
+}
+
+/// Writes account identification of a party.
+fn write_account<W: Write>(
+    xml: &mut XmlWriter<W>,
+    tag: &'static str,
+    party: &TransactionParty,
+) -> Result<()> {
+    xml.start(tag)?;
+    xml.start("Id")?;
+    match party.iban() {
+        Some(iban) => xml.text("IBAN", iban.electronic())?,
+        None => {
+            xml.start("Othr")?;
+            xml.text(
+                "Id",
+                &truncate(&party.account_identifier, MAX_ACCOUNT_ID_LENGTH),
+            )?;
+            xml.end()?;
+        }
+    }
+    xml.end()?;
+    xml.end()?;
+    Ok(())
+}
+
+/// Writes name of a party.
+fn write_party<W: Write>(
+    xml: &mut XmlWriter<W>,
+    tag: &'static str,
+    party: &TransactionParty,
+) -> Result<()> {
+    xml.start(tag)?;
+    if !party.account_name.is_empty() {
+        xml.text("Nm", &truncate(&party.account_name, MAX_NAME_LENGTH))?;
+    }
+    xml.end()?;
+    Ok(())
+}
+
+/// Writes financial institution of a party.
+fn write_agent<W: Write>(
+    xml: &mut XmlWriter<W>,
+    tag: &'static str,
+    party: &TransactionParty,
+) -> Result<()> {
+    if let Some(bic) = party.resolved_bic() {
+        xml.start(tag)?;
+        xml.start("FinInstnId")?;
+        xml.text("BIC", bic.as_str())?;
+        xml.end()?;
+        xml.end()?;
+    }
+    Ok(())
+}
+
+/// Writes a single entry of the statement.
+fn write_entry<W: Write>(xml: &mut XmlWriter<W>, transaction: &Transaction) -> Result<()> {
+    let amount = signed_amount(transaction);
+    xml.start("Ntry")?;
+    xml.text(
+        "NtryRef",
+        &truncate(&transaction.transaction_id, MAX_ID_LENGTH),
+    )?;
+    xml.text_with(
+        "Amt",
+        &[("Ccy", &transaction.currency)],
+        &format_abs(amount),
+    )?;
+    xml.text("CdtDbtInd", indicator(amount))?;
+    xml.text("Sts", "BOOK")?;
+    xml.start("BookgDt")?;
+    xml.text("Dt", &local_date(&transaction.booking_datetime).to_string())?;
+    xml.end()?;
+    xml.start("ValDt")?;
+    xml.text("Dt", &local_date(&transaction.value_datetaime).to_string())?;
+    xml.end()?;
+    if let Some(archive_id) = &transaction.archive_id {
+        xml.text("AcctSvcrRef", &truncate(archive_id, MAX_ID_LENGTH))?;
+    }
+    write_bank_transaction_code(xml, transaction)?;
+
+    xml.start("NtryDtls")?;
+    xml.start("TxDtls")?;
+    xml.start("Refs")?;
+    if let Some(archive_id) = &transaction.archive_id {
+        xml.text("AcctSvcrRef", &truncate(archive_id, MAX_ID_LENGTH))?;
+    }
+    xml.text(
+        "TxId",
+        &truncate(&transaction.transaction_id, MAX_ID_LENGTH),
+    )?;
+    xml.end()?;
+    if transaction.creditor.is_some() || transaction.debtor.is_some() {
+        xml.start("RltdPties")?;
+        if let Some(debtor) = &transaction.debtor {
+            write_party(xml, "Dbtr", debtor)?;
+            write_account(xml, "DbtrAcct", debtor)?;
+        }
+        if let Some(creditor) = &transaction.creditor {
+            write_party(xml, "Cdtr", creditor)?;
+            write_account(xml, "CdtrAcct", creditor)?;
+        }
+        xml.end()?;
+        let has_agents = [&transaction.debtor, &transaction.creditor]
+            .iter()
+            .any(|p| p.as_ref().and_then(|p| p.resolved_bic()).is_some());
+        if has_agents {
+            xml.start("RltdAgts")?;
+            if let Some(debtor) = &transaction.debtor {
+                write_agent(xml, "DbtrAgt", debtor)?;
+            }
+            if let Some(creditor) = &transaction.creditor {
+                write_agent(xml, "CdtrAgt", creditor)?;
+            }
+            xml.end()?;
+        }
+    }
+    write_remittance_information(xml, transaction)?;
+    xml.end()?;
+    xml.end()?;
+    xml.end()?;
+    Ok(())
+}
+
+/// Writes ISO 20022 and OP transaction codes of the entry.
+///
+/// The element is mandatory, so entries without codes get the
+/// proprietary code NTAV.
+fn write_bank_transaction_code<W: Write>(
+    xml: &mut XmlWriter<W>,
+    transaction: &Transaction,
+) -> Result<()> {
+    let code = transaction.bank_transaction_code();
+    xml.start("BkTxCd")?;
+    if let Some(code) = &code {
+        xml.start("Domn")?;
+        xml.text("Cd", code.domain())?;
+        xml.start("Fmly")?;
+        xml.text("Cd", code.family())?;
+        xml.text("SubFmlyCd", code.sub_family())?;
+        xml.end()?;
+        xml.end()?;
+    }
+    let op_code = transaction.op_transaction_code.as_deref();
+    if op_code.is_some() || code.is_none() {
+        xml.start("Prtry")?;
+        xml.text(
+            "Cd",
+            &truncate(op_code.unwrap_or(NOT_AVAILABLE), MAX_ID_LENGTH),
+        )?;
+        xml.end()?;
+    }
+    xml.end()?;
+    Ok(())
+}
+
+/// Writes message and reference number of the entry.
+fn write_remittance_information<W: Write>(
+    xml: &mut XmlWriter<W>,
+    transaction: &Transaction,
+) -> Result<()> {
+    let message = transaction.message.as_deref().filter(|m| !m.is_empty());
+    let reference = transaction.reference_number();
+    if message.is_none() && reference.is_none() {
+        return Ok(());
+    }
+    xml.start("RmtInf")?;
+    if let Some(message) = message {
+        xml.text("Ustrd", &truncate(message, MAX_MESSAGE_LENGTH))?;
+    }
+    if let Some(reference) = reference {
+        xml.start("Strd")?;
+        xml.start("CdtrRefInf")?;
+        xml.start("Tp")?;
+        xml.start("CdOrPrtry")?;
+        xml.text("Cd", "SCOR")?;
+        xml.end()?;
+        if reference.kind() == ReferenceKind::Rf {
+            xml.text("Issr", "ISO")?;
+        }
+        xml.end()?;
+        xml.text("Ref", reference.electronic())?;
+        xml.end()?;
+        xml.end()?;
+    }
+    xml.end()?;
+    Ok(())
+}
+
+/// Writes camt.053 statement of the account for the period from the
+/// transactions.
+///
+/// Only transactions booked during the period in Europe/Helsinki are
+/// included. If there are no transactions in a period which has not
+/// ended yet, the current balance of the account is used for both
+/// opening and closing balance. Past periods without transactions are
+/// rejected with ExportError::InvalidData.
+pub fn write_statement<W: Write>(
+    writer: W,
+    account: &Account,
+    from: NaiveDate,
+    to: NaiveDate,
+    transactions: &[Transaction],
+) -> Result<()> {
+    let statement = Statement::new(account, from, to, transactions)?;
+    let end = time::end_of_day(to).ok_or_else(|| {
+        ExportError::InvalidData(format!(
+            "End of the statement period {} is out of range",
+            to
+        ))
+    })?;
+    let created = time::local_now().format(DATETIME_FORMAT).to_string();
+    let id = truncate(&statement.id(), MAX_ID_LENGTH);
+
+    let mut xml = XmlWriter::new(writer)?;
+    xml.start_with("Document", &[("xmlns", NAMESPACE)])?;
+    xml.start("BkToCstmrStmt")?;
+    xml.start("GrpHdr")?;
+    xml.text("MsgId", &id)?;
+    xml.text("CreDtTm", &created)?;
+    xml.end()?;
+
+    xml.start("Stmt")?;
+    xml.text("Id", &id)?;
+    xml.text("CreDtTm", &created)?;
+    xml.start("FrToDt")?;
+    xml.text(
+        "FrDtTm",
+        &time::start_of_day(from).format(DATETIME_FORMAT).to_string(),
+    )?;
+    xml.text("ToDtTm", &end.format(DATETIME_FORMAT).to_string())?;
+    xml.end()?;
+
+    xml.start("Acct")?;
+    xml.start("Id")?;
+    match account.iban() {
+        Some(iban) => xml.text("IBAN", iban.electronic())?,
+        None => {
+            xml.start("Othr")?;
+            xml.text("Id", &truncate(&account.identifier, MAX_ACCOUNT_ID_LENGTH))?;
+            xml.end()?;
+        }
+    }
+    xml.end()?;
+    xml.text("Ccy", &account.currency)?;
+    if !account.name.is_empty() {
+        xml.text("Nm", &truncate(&account.name, MAX_ACCOUNT_NAME_LENGTH))?;
+    }
+    if let Some(bic) = account.resolved_bic() {
+        xml.start("Svcr")?;
+        xml.start("FinInstnId")?;
+        xml.text("BIC", bic.as_str())?;
+        xml.end()?;
+        xml.end()?;
+    }
+    xml.end()?;
+
+    write_balance(&mut xml, "OPBD", statement.opening, &account.currency, from)?;
+    write_balance(&mut xml, "CLBD", statement.closing, &account.currency, to)?;
+
+    let (credits, debits): (Vec<Amount>, Vec<Amount>) = statement
+        .entries
+        .iter()
+        .map(|t| signed_amount(t))
+        .partition(|amount| *amount >= Amount::default());
+    xml.start("TxsSummry")?;
+    xml.start("TtlNtries")?;
+    xml.text("NbOfNtries", &statement.entries.len().to_string())?;
+    xml.end()?;
+    for (tag, amounts) in [("TtlCdtNtries", credits), ("TtlDbtNtries", debits)] {
+        let sum = amounts.iter().fold(Amount::default(), |sum, a| sum + *a);
+        xml.start(tag)?;
+        xml.text("NbOfNtries", &amounts.len().to_string())?;
+        xml.text("Sum", &format_abs(sum))?;
+        xml.end()?;
+    }
+    xml.end()?;
+
+    for transaction in statement.entries.iter() {
+        write_entry(&mut xml, transaction)?;
+    }
+    xml.finish()?;
+    Ok(())
+}
+
+/// Returns camt.053 statement of the account for the period as string.
+pub fn to_string(
+    account: &Account,
+    from: NaiveDate,
+    to: NaiveDate,
+    transactions: &[Transaction],
+) -> Result<String> {
+    let mut output = Vec::new();
+    write_statement(&mut output, account, from, to, transactions)?;
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
@@ -0,0 +1,274 @@
+//! Export of transactions to CSV.
+//!
+//! Columns, number and date formats and the delimiter are configurable
+//! with CsvOptions. Counterparty fields of the creditor and debtor are
+//! flattened into separate columns.
+
+use super::{local_date, signed_amount, Locale, Result};
+use crate::model::accounts::{Transaction, TransactionParty};
+use std::io::Write;
+
+/// Column of the CSV export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvColumn {
+    /// Surrogate identifier of the transaction.
+    TransactionId,
+    /// Surrogate identifier of the account.
+    AccountId,
+    /// Archive ID of the transaction.
+    ArchiveId,
+    /// Local booking date.
+    BookingDate,
+    /// Local value date.
+    ValueDate,
+    /// Amount of the transaction, negative for debits.
+    Amount,
+    /// Currency of the transaction.
+    Currency,
+    /// Credit or debit.
+    CreditDebitIndicator,
+    /// Balance of the account after the transaction.
+    Balance,
+    /// Reference number.
+    Reference,
+    /// Message of the transaction.
+    Message,
+    /// Status of the transaction.
+    Status,
+    /// ISO 20022 bank transaction code.
+    IsoTransactionCode,
+    /// OP transaction code.
+    OpTransactionCode,
+    /// Name of the creditor of debits or debtor of credits.
+    CounterpartyName,
+    /// Account of the creditor of debits or debtor of credits.
+    CounterpartyAccount,
+    /// Servicer of the creditor of debits or debtor of credits.
+    CounterpartyServicer,
+    /// Name of the creditor.
+    CreditorName,
+    /// Account of the creditor.
+    CreditorAccount,
+    /// Servicer of the creditor.
+    CreditorServicer,
+    /// Name of the debtor.
+    DebtorName,
+    /// Account of the debtor.
+    DebtorAccount,
+    /// Servicer of the debtor.
+    DebtorServicer,
+}
+
+/// Implementation of the CsvColumn.
+impl CsvColumn {
+    /// Returns header of the column in the locale.
+    pub fn header(&self, locale: Locale) -> &'static str {
+        let (finnish, english) = match self {
+            CsvColumn::TransactionId => ("Tapahtuman tunnus", "Transaction ID"),
+            CsvColumn::AccountId => ("Tilin tunnus", "Account ID"),
+            CsvColumn::ArchiveId => ("Arkistointitunnus", "Archive ID"),
+            CsvColumn::BookingDate => ("Kirjauspäivä", "Booking date"),
+            CsvColumn::ValueDate => ("Arvopäivä", "Value date"),
+            CsvColumn::Amount => ("Määrä", "Amount"),
+            CsvColumn::Currency => ("Valuutta", "Currency"),
+            CsvColumn::CreditDebitIndicator => ("Tyyppi", "Credit/debit"),
+            CsvColumn::Balance => ("Saldo", "Balance"),
+            CsvColumn::Reference => ("Viite", "Reference"),
+            CsvColumn::Message => ("Viesti", "Message"),
+            CsvColumn::Status => ("Tila", "Status"),
+            CsvColumn::IsoTransactionCode => ("ISO-tapahtumakoodi", "ISO transaction code"),
+            CsvColumn::OpTransactionCode => ("Tapahtumalaji", "Transaction code"),
+            CsvColumn::CounterpartyName => ("Saaja/Maksaja", "Counterparty"),
+            CsvColumn::CounterpartyAccount => ("Vastatili", "Counterparty account"),
+            CsvColumn::CounterpartyServicer => ("Vastapankki", "Counterparty bank"),
+            CsvColumn::CreditorName => ("Saaja", "Creditor"),
+            CsvColumn::CreditorAccount => ("Saajan tili", "Creditor account"),
+            CsvColumn::CreditorServicer => ("Saajan pankki", "Creditor bank"),
+            CsvColumn::DebtorName => ("Maksaja", "Debtor"),
+            CsvColumn::DebtorAccount => ("Maksajan tili", "Debtor account"),
+            CsvColumn::DebtorServicer => ("Maksajan pankki", "Debtor bank"),
+        };
+        match locale {
+            Locale::Finnish => finnish,
+            Locale::International => english,
+        }
+    }
+
+    /// Returns value of the column for the transaction.
+    fn value(&self, transaction: &Transaction, locale: Locale) -> String {
+        match self {
+            CsvColumn::TransactionId => transaction.transaction_id.clone(),
+            CsvColumn::AccountId => transaction.account_id.clone(),
+            CsvColumn::ArchiveId => optional(&transaction.archive_id),
+            CsvColumn::BookingDate => locale.format_date(local_date(&transaction.booking_datetime)),
+            CsvColumn::ValueDate => locale.format_date(local_date(&transaction.value_datetaime)),
+            CsvColumn::Amount => locale.format_amount(signed_amount(transaction)),
+            CsvColumn::Currency => transaction.currency.clone(),
+            CsvColumn::CreditDebitIndicator => transaction.credit_debit_indicator.to_string(),
+            CsvColumn::Balance => locale.format_amount(transaction.account_balance),
+            CsvColumn::Reference => optional(&transaction.reference),
+            CsvColumn::Message => optional(&transaction.message),
+            CsvColumn::Status => transaction
+                .status
+                .as_ref()
+                .map(|status| status.to_string())
+                .unwrap_or_default(),
+            CsvColumn::IsoTransactionCode => optional(&transaction.iso_transaction_code),
+            CsvColumn::OpTransactionCode => optional(&transaction.op_transaction_code),
+            CsvColumn::CounterpartyName => name(transaction.counterparty()),
+            CsvColumn::CounterpartyAccount => account(transaction.counterparty()),
+            CsvColumn::CounterpartyServicer => servicer(transaction.counterparty()),
+            CsvColumn::CreditorName => name(transaction.creditor.as_ref()),
+            CsvColumn::CreditorAccount => account(transaction.creditor.as_ref()),
+            CsvColumn::CreditorServicer => servicer(transaction.creditor.as_ref()),
+            CsvColumn::DebtorName => name(transaction.debtor.as_ref()),
+            CsvColumn::DebtorAccount => account(transaction.debtor.as_ref()),
+            CsvColumn::DebtorServicer => servicer(transaction.debtor.as_ref()),
+        }
+    }
+}
+
+/// Returns the value or empty string.
+fn optional(value: &Option<String>) -> String {
+    value.clone().unwrap_or_default()
+}
+
+/// Returns name of the party or empty string.
+fn name(party: Option<&TransactionParty>) -> String {
+    party.map(|p| p.account_name.clone()).unwrap_or_default()
+}
+
+/// Returns account of the party or empty string.
+fn account(party: Option<&TransactionParty>) -> String {
+    party
+        .map(|p| p.account_identifier.clone())
+        .unwrap_or_default()
+}
+
+/// Returns servicer of the party or empty string.
+///
+/// Missing BICs of Finnish accounts are looked up from the IBAN.
+fn servicer(party: Option<&TransactionParty>) -> String {
+    party
+        .map(|p| match p.resolved_bic() {
+            Some(bic) => bic.to_string(),
+            None => p.servicer_identifier.clone(),
+        })
+        .unwrap_or_default()
+}
+
+/// Default columns of the export.
+const DEFAULT_COLUMNS: [CsvColumn; 10] = [
+    CsvColumn::BookingDate,
+    CsvColumn::ValueDate,
+    CsvColumn::Amount,
+    CsvColumn::Currency,
+    CsvColumn::CounterpartyName,
+    CsvColumn::CounterpartyAccount,
+    CsvColumn::Reference,
+    CsvColumn::Message,
+    CsvColumn::ArchiveId,
+    CsvColumn::TransactionId,
+];
+
+/// Options of the CSV export.
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// Columns in the order they are written.
+    pub columns: Vec<CsvColumn>,
+    /// Format of the numbers, dates and headers.
+    pub locale: Locale,
+    /// Delimiter between the fields.
+    pub delimiter: u8,
+    /// Whether a header row is written.
+    pub headers: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions::international()
+    }
+}
+
+/// Implementation of the CsvOptions.
+impl CsvOptions {
+    /// Returns options for Finnish spreadsheets with decimal comma and
+    /// semicolon as delimiter.
+    pub fn finnish() -> Self {
+        CsvOptions {
+            columns: DEFAULT_COLUMNS.to_vec(),
+            locale: Locale::Finnish,
+            delimiter: b';',
+            headers: true,
+        }
+    }
+
+    /// Returns options with decimal point, ISO 8601 dates and comma as
+    /// delimiter.
+    pub fn international() -> Self {
+        CsvOptions {
+            columns: DEFAULT_COLUMNS.to_vec(),
+            locale: Locale::International,
+            delimiter: b',',
+            headers: true,
+        }
+    }
+
+    /// Sets columns of the export.
+    pub fn with_columns(mut self, columns: Vec<CsvColumn>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sets format of the numbers, dates and headers.
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Sets delimiter between the fields.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets whether a header row is written.
+    pub fn with_headers(mut self, headers: bool) -> Self {
+        self.headers = headers;
+        self
+    }
+}
+
+/// Writes transactions to the writer as CSV.
+pub fn write_transactions<'a, W, I>(writer: W, transactions: I, options: &CsvOptions) -> Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a Transaction>,
+{
+    let mut csv = ::csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .from_writer(writer);
+    if options.headers {
+        csv.write_record(options.columns.iter().map(|c| c.header(options.locale)))
+            .map_err(std::io::Error::from)?;
+    }
+    for transaction in transactions {
+        let record = options
+            .columns
+            .iter()
+            .map(|c| c.value(transaction, options.locale));
+        csv.write_record(record).map_err(std::io::Error::from)?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// Returns transactions as CSV string.
+pub fn to_string<'a, I>(transactions: I, options: &CsvOptions) -> Result<String>
+where
+    I: IntoIterator<Item = &'a Transaction>,
+{
+    let mut output = Vec::new();
+    write_transactions(&mut output, transactions, options)?;
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
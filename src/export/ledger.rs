@@ -0,0 +1,407 @@
+//! Export of transactions to plain text accounting journals.
+//!
+//! Journals can be written for ledger, hledger and beancount. The bank
+//! account posting of each transaction is balanced against an account
+//! chosen by the first matching AccountRule, or the default income or
+//! expense account. Balance assertions are generated from the account
+//! balances of the transactions.
+
+use super::{local_date, signed_amount, single_line, sort_by_booking, ExportError, Locale, Result};
+use crate::banking::{Iban, Reference};
+use crate::model::accounts::{Transaction, TransactionStatus};
+use crate::model::money::Amount;
+use chrono::{Duration, NaiveDate};
+use regex::Regex;
+use std::io::Write;
+
+/// Plain text accounting format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerFormat {
+    /// Journal of ledger.
+    Ledger,
+    /// Journal of hledger with descriptions split to payee and note.
+    Hledger,
+    /// Beancount input file.
+    Beancount,
+}
+
+/// Condition of an account rule.
+#[derive(Debug, Clone)]
+enum Matcher {
+    Iban(Iban),
+    Reference(Reference),
+    Message(Regex),
+}
+
+/// Rule mapping matching transactions to an account.
+#[derive(Debug, Clone)]
+pub struct AccountRule {
+    matcher: Matcher,
+    account: String,
+}
+
+/// Implementation of the AccountRule.
+impl AccountRule {
+    /// Creates rule matching transactions with the counterparty IBAN.
+    pub fn iban(iban: Iban, account: &str) -> Self {
+        AccountRule {
+            matcher: Matcher::Iban(iban),
+            account: account.to_string(),
+        }
+    }
+
+    /// Creates rule matching transactions with the reference number.
+    pub fn reference(reference: Reference, account: &str) -> Self {
+        AccountRule {
+            matcher: Matcher::Reference(reference),
+            account: account.to_string(),
+        }
+    }
+
+    /// Creates rule matching transactions with message matching the
+    /// regular expression.
+    pub fn message(pattern: &str, account: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| {
+            ExportError::InvalidData(format!("Invalid message pattern {}: {}", pattern, e))
+        })?;
+        Ok(AccountRule {
+            matcher: Matcher::Message(regex),
+            account: account.to_string(),
+        })
+    }
+
+    /// Returns account of the rule.
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// Returns whether the transaction matches the rule.
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        match &self.matcher {
+            Matcher::Iban(iban) => transaction
+                .counterparty()
+                .and_then(|p| p.iban())
+                .is_some_and(|i| &i == iban),
+            Matcher::Reference(reference) => transaction
+                .reference_number()
+                .is_some_and(|r| &r == reference),
+            Matcher::Message(regex) => transaction
+                .message
+                .as_deref()
+                .is_some_and(|m| regex.is_match(m)),
+        }
+    }
+}
+
+/// Options of the plain text accounting export.
+#[derive(Debug, Clone)]
+pub struct LedgerOptions {
+    /// Format of the journal.
+    pub format: LedgerFormat,
+    /// Account of the bank account.
+    pub account: String,
+    /// Account for credits matching no rule.
+    pub income_account: String,
+    /// Account for debits matching no rule.
+    pub expense_account: String,
+    /// Rules in the order they are tried.
+    pub rules: Vec<AccountRule>,
+    /// Whether balance assertions are written.
+    pub balance_assertions: bool,
+}
+
+impl Default for LedgerOptions {
+    fn default() -> Self {
+        LedgerOptions::new(LedgerFormat::Ledger)
+    }
+}
+
+/// Implementation of the LedgerOptions.
+impl LedgerOptions {
+    /// Returns options for the format with uncategorized default accounts.
+    pub fn new(format: LedgerFormat) -> Self {
+        LedgerOptions {
+            format,
+            account: "Assets:Bank:OP".to_string(),
+            income_account: "Income:Uncategorized".to_string(),
+            expense_account: "Expenses:Uncategorized".to_string(),
+            rules: Vec::new(),
+            balance_assertions: true,
+        }
+    }
+
+    /// Sets account of the bank account.
+    pub fn with_account(mut self, account: &str) -> Self {
+        self.account = account.to_string();
+        self
+    }
+
+    /// Sets account for credits matching no rule.
+    pub fn with_income_account(mut self, account: &str) -> Self {
+        self.income_account = account.to_string();
+        self
+    }
+
+    /// Sets account for debits matching no rule.
+    pub fn with_expense_account(mut self, account: &str) -> Self {
+        self.expense_account = account.to_string();
+        self
+    }
+
+    /// Adds rule after the existing rules.
+    pub fn with_rule(mut self, rule: AccountRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Sets whether balance assertions are written.
+    pub fn with_balance_assertions(mut self, balance_assertions: bool) -> Self {
+        self.balance_assertions = balance_assertions;
+        self
+    }
+
+    /// Returns account balancing the transaction.
+    pub fn contra_account(&self, transaction: &Transaction) -> &str {
+        match self.rules.iter().find(|rule| rule.matches(transaction)) {
+            Some(rule) => rule.account(),
+            None if signed_amount(transaction) < Amount::default() => &self.expense_account,
+            None => &self.income_account,
+        }
+    }
+}
+
+/// Formats amount with the currency.
+fn format_amount(amount: Amount, currency: &str) -> String {
+    format!(
+        "{} {}",
+        Locale::International.format_amount(amount),
+        currency
+    )
+}
+
+/// Escapes string for beancount.
+fn quote(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        single_line(value)
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+    )
+}
+
+/// Returns whether the transaction was rejected or revoked and never
+/// affected the balance.
+fn is_cancelled(transaction: &Transaction) -> bool {
+    matches!(
+        transaction.status,
+        Some(TransactionStatus::Rejected) | Some(TransactionStatus::Revoked)
+    )
+}
+
+/// Returns status flag of the transaction.
+fn flag(transaction: &Transaction) -> &'static str {
+    match transaction.status {
+        Some(TransactionStatus::AwaitingAuthorisation) => "!",
+        _ => "*",
+    }
+}
+
+/// Returns payee of the transaction.
+fn payee(transaction: &Transaction) -> Option<String> {
+    transaction
+        .counterparty()
+        .map(|p| single_line(&p.account_name))
+        .filter(|name| !name.is_empty())
+}
+
+/// Returns message of the transaction, or the reference if there is no
+/// message.
+fn narration(transaction: &Transaction) -> Option<String> {
+    transaction
+        .message
+        .as_deref()
+        .filter(|m| !m.is_empty())
+        .or_else(|| transaction.reference.as_deref().filter(|r| !r.is_empty()))
+        .map(single_line)
+}
+
+/// Replaces sequences which ledger and hledger would parse from the
+/// text of the entry.
+///
+/// A colon followed by a space starts a metadata tag in comments and the
+/// first pipe splits the hledger description into payee and note.
+fn escape_text(value: &str) -> String {
+    value.replace(": ", " - ").replace('|', "/")
+}
+
+/// Writes transaction as ledger or hledger entry.
+fn write_ledger_entry<W: Write>(
+    writer: &mut W,
+    transaction: &Transaction,
+    options: &LedgerOptions,
+) -> Result<()> {
+    let amount = signed_amount(transaction);
+    let currency = &transaction.currency;
+    write!(
+        writer,
+        "{} {}",
+        local_date(&transaction.booking_datetime),
+        flag(transaction)
+    )?;
+    if let Some(archive_id) = &transaction.archive_id {
+        write!(writer, " ({})", single_line(archive_id))?;
+    }
+    let payee = payee(transaction).map(|p| escape_text(&p));
+    let narration = narration(transaction).map(|n| escape_text(&n));
+    match (options.format, payee, narration) {
+        (LedgerFormat::Hledger, Some(payee), Some(narration)) => {
+            writeln!(writer, " {} | {}", payee, narration)?
+        }
+        (_, Some(payee), narration) => {
+            writeln!(writer, " {}", payee)?;
+            if let Some(narration) = narration {
+                writeln!(writer, "    ; {}", narration)?;
+            }
+        }
+        (_, None, Some(narration)) => writeln!(writer, " {}", narration)?,
+        (_, None, None) => writeln!(writer)?,
+    }
+    writeln!(
+        writer,
+        "    ; transaction_id: {}",
+        single_line(&transaction.transaction_id)
+    )?;
+    write!(
+        writer,
+        "    {}  {}",
+        options.account,
+        format_amount(amount, currency)
+    )?;
+    if options.balance_assertions {
+        write!(
+            writer,
+            " = {}",
+            format_amount(transaction.account_balance, currency)
+        )?;
+    }
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "    {}  {}",
+        options.contra_account(transaction),
+        format_amount(-amount, currency)
+    )?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Writes transaction as beancount entry.
+fn write_beancount_entry<W: Write>(
+    writer: &mut W,
+    transaction: &Transaction,
+    options: &LedgerOptions,
+) -> Result<()> {
+    let amount = signed_amount(transaction);
+    let currency = &transaction.currency;
+    write!(
+        writer,
+        "{} {}",
+        local_date(&transaction.booking_datetime),
+        flag(transaction)
+    )?;
+    if let Some(payee) = payee(transaction) {
+        write!(writer, " {}", quote(&payee))?;
+    }
+    writeln!(
+        writer,
+        " {}",
+        quote(&narration(transaction).unwrap_or_default())
+    )?;
+    writeln!(
+        writer,
+        "  transaction_id: {}",
+        quote(&transaction.transaction_id)
+    )?;
+    if let Some(archive_id) = &transaction.archive_id {
+        writeln!(writer, "  archive_id: {}", quote(archive_id))?;
+    }
+    writeln!(
+        writer,
+        "  {}  {}",
+        options.account,
+        format_amount(amount, currency)
+    )?;
+    writeln!(
+        writer,
+        "  {}  {}",
+        options.contra_account(transaction),
+        format_amount(-amount, currency)
+    )?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Writes beancount balance assertion for the end of the date.
+///
+/// Beancount checks balances at the start of the day, so the assertion
+/// is dated on the following day.
+fn write_beancount_balance<W: Write>(
+    writer: &mut W,
+    date: NaiveDate,
+    transaction: &Transaction,
+    options: &LedgerOptions,
+) -> Result<()> {
+    writeln!(
+        writer,
+        "{} balance {}  {}",
+        date + Duration::days(1),
+        options.account,
+        format_amount(transaction.account_balance, &transaction.currency)
+    )?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Writes transactions as plain text accounting journal.
+///
+/// Transactions are written in booking order dated with the local booking
+/// date. Rejected and revoked transactions are left out. Ledger and hledger balance assertions are attached to the bank
+/// account postings, whereas beancount gets a balance directive for the
+/// end of each booking date. Accounts of beancount must be opened
+/// elsewhere in the books.
+pub fn write_journal<W: Write>(
+    mut writer: W,
+    transactions: &[Transaction],
+    options: &LedgerOptions,
+) -> Result<()> {
+    let mut entries: Vec<&Transaction> = transactions.iter().filter(|t| !is_cancelled(t)).collect();
+    sort_by_booking(&mut entries);
+
+    for (i, transaction) in entries.iter().enumerate() {
+        match options.format {
+            LedgerFormat::Ledger | LedgerFormat::Hledger => {
+                write_ledger_entry(&mut writer, transaction, options)?
+            }
+            LedgerFormat::Beancount => {
+                write_beancount_entry(&mut writer, transaction, options)?;
+                let date = local_date(&transaction.booking_datetime);
+                let last_of_date = match entries.get(i + 1) {
+                    Some(next) => local_date(&next.booking_datetime) != date,
+                    None => true,
+                };
+                if options.balance_assertions && last_of_date {
+                    write_beancount_balance(&mut writer, date, transaction, options)?;
+                }
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Returns transactions as plain text accounting journal.
+pub fn to_string(transactions: &[Transaction], options: &LedgerOptions) -> Result<String> {
+    let mut output = Vec::new();
+    write_journal(&mut output, transactions, options)?;
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
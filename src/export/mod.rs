@@ -0,0 +1,200 @@
+//! This module contains writers exporting transactions from the
+//! Accounts API to file formats used by spreadsheets and accounting
+//! software, such as CSV, ISO 20022 camt.053, Finnish TITO, OFX, QIF and
+//! the plain text accounting formats of ledger, hledger and beancount.
+
+use crate::model::accounts::{Account, CreditDebitIndicator, Transaction};
+use crate::model::money::Amount;
+use crate::model::time;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::fmt;
+use std::io;
+
+pub mod camt053;
+pub mod csv;
+pub mod ledger;
+pub mod ofx;
+pub mod qif;
+pub mod tito;
+mod xml;
+
+/// Result of the export functions.
+pub type Result<T> = std::result::Result<T, ExportError>;
+
+/// Error in exporting transactions.
+#[derive(Debug)]
+pub enum ExportError {
+    /// Writing the output failed.
+    Io(io::Error),
+    /// Transactions can not be represented in the format.
+    InvalidData(String),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Io(e) => write!(f, "Writing export failed: {}", e),
+            ExportError::InvalidData(message) => write!(f, "Invalid export data: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExportError::Io(e) => Some(e),
+            ExportError::InvalidData(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::Io(e)
+    }
+}
+
+/// Conventions for formatting numbers and dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    /// Decimal comma and dates like 31.12.2020.
+    Finnish,
+    /// Decimal point and ISO 8601 dates like 2020-12-31.
+    International,
+}
+
+/// Implementation of the Locale.
+impl Locale {
+    /// Formats amount with two decimals.
+    pub fn format_amount(&self, amount: Amount) -> String {
+        let formatted = format!("{:.2}", amount);
+        match self {
+            Locale::Finnish => formatted.replace('.', ","),
+            Locale::International => formatted,
+        }
+    }
+
+    /// Formats date.
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        match self {
+            Locale::Finnish => date.format("%-d.%-m.%Y").to_string(),
+            Locale::International => date.format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+/// Returns the local date of the date-time in Europe/Helsinki.
+pub(crate) fn local_date(datetime: &DateTime<Utc>) -> NaiveDate {
+    time::to_local(datetime).date()
+}
+
+/// Replaces line breaks with spaces for line-based formats.
+pub(crate) fn single_line(value: &str) -> String {
+    value.replace("\r\n", " ").replace(['\r', '\n'], " ")
+}
+
+/// Truncates the value to the maximum number of characters.
+pub(crate) fn truncate(value: &str, max: usize) -> String {
+    value.chars().take(max).collect()
+}
+
+/// Returns amount of the transaction, negative for debits.
+pub(crate) fn signed_amount(transaction: &Transaction) -> Amount {
+    match transaction.credit_debit_indicator {
+        CreditDebitIndicator::Credit => transaction.amount.abs(),
+        CreditDebitIndicator::Debit => -transaction.amount.abs(),
+        CreditDebitIndicator::Unknown(_) => transaction.amount,
+    }
+}
+
+/// Sorts the transactions in booking order.
+///
+/// Transactions booked at the same time are ordered by their IDs so that
+/// the order does not depend on the order returned by the API.
+pub(crate) fn sort_by_booking(entries: &mut [&Transaction]) {
+    entries.sort_by(|a, b| {
+        a.booking_datetime
+            .cmp(&b.booking_datetime)
+            .then_with(|| a.transaction_id.cmp(&b.transaction_id))
+    });
+}
+
+/// Account statement of a period.
+pub(crate) struct Statement<'a> {
+    pub(crate) account: &'a Account,
+    pub(crate) from: NaiveDate,
+    pub(crate) to: NaiveDate,
+    pub(crate) entries: Vec<&'a Transaction>,
+    pub(crate) opening: Amount,
+    pub(crate) closing: Amount,
+}
+
+/// Implementation of the Statement.
+impl<'a> Statement<'a> {
+    /// Creates statement from the transactions booked during the period.
+    ///
+    /// The current balance of the account is used for both opening and
+    /// closing balance of a period without transactions only if the
+    /// period has not ended yet. Past periods without transactions are
+    /// rejected, because their balances are unknown.
+    pub(crate) fn new(
+        account: &'a Account,
+        from: NaiveDate,
+        to: NaiveDate,
+        transactions: &'a [Transaction],
+    ) -> Result<Statement<'a>> {
+        if from > to {
+            return Err(ExportError::InvalidData(format!(
+                "Statement period start {} is after the end {}",
+                from, to
+            )));
+        }
+        let mut entries: Vec<&Transaction> = transactions
+            .iter()
+            .filter(|t| {
+                let date = local_date(&t.booking_datetime);
+                date >= from && date <= to
+            })
+            .collect();
+        sort_by_booking(&mut entries);
+
+        let (opening, closing) = match (entries.first(), entries.last()) {
+            (Some(first), Some(last)) => (
+                first.account_balance - signed_amount(first),
+                last.account_balance,
+            ),
+            _ if to < time::local_today() => {
+                return Err(ExportError::InvalidData(format!(
+                    "No transactions for the balances of the past period {} - {}",
+                    from, to
+                )));
+            }
+            _ => {
+                let balance = account.balance.ok_or_else(|| {
+                    ExportError::InvalidData(
+                        "No transactions or account balance for the statement".to_string(),
+                    )
+                })?;
+                (balance, balance)
+            }
+        };
+        Ok(Statement {
+            account,
+            from,
+            to,
+            entries,
+            opening,
+            closing,
+        })
+    }
+
+    /// Returns identifier of the statement.
+    pub(crate) fn id(&self) -> String {
+        format!(
+            "{}-{}-{}",
+            self.account.account_id,
+            self.from.format("%Y%m%d"),
+            self.to.format("%Y%m%d")
+        )
+    }
+}
@@ -0,0 +1,221 @@
+//! Export of account statements as OFX.
+//!
+//! Statements can be written as OFX 1.0.2 SGML or OFX 2.1.1 XML, which
+//! are understood by personal finance software such as GnuCash and
+//! Moneydance. Transaction IDs are used as FITIDs so that importing an
+//! overlapping statement again does not create duplicates.
+
+use super::xml::XmlWriter;
+use super::{signed_amount, sort_by_booking, truncate, ExportError, Locale, Result};
+use crate::banking::TransactionCategory;
+use crate::model::accounts::{Account, Transaction};
+use crate::model::money::Amount;
+use chrono::{DateTime, Utc};
+use std::io::Write;
+
+/// Maximum length of the payee name.
+const MAX_NAME_LENGTH: usize = 32;
+
+/// Maximum length of the memo.
+const MAX_MEMO_LENGTH: usize = 255;
+
+/// Maximum length of the reference number.
+const MAX_REFNUM_LENGTH: usize = 32;
+
+/// Maximum length of the bank identifier.
+const MAX_BANKID_LENGTH: usize = 9;
+
+/// Format of the date-times in the document.
+const DATETIME_FORMAT: &str = "%Y%m%d%H%M%S.000[0:GMT]";
+
+/// Format of the OFX document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OfxFormat {
+    /// OFX 1.0.2 with SGML header and unclosed elements.
+    #[default]
+    Sgml,
+    /// OFX 2.1.1 XML document.
+    Xml,
+}
+
+/// Formats UTC date-time.
+fn format_datetime(datetime: &DateTime<Utc>) -> String {
+    datetime.format(DATETIME_FORMAT).to_string()
+}
+
+/// Returns OFX transaction type of the transaction.
+fn transaction_type(transaction: &Transaction, amount: Amount) -> &'static str {
+    match transaction.category() {
+        TransactionCategory::Interest => "INT",
+        TransactionCategory::Fee => "FEE",
+        TransactionCategory::AtmWithdrawal => "ATM",
+        TransactionCategory::CardPurchase => "POS",
+        TransactionCategory::DirectDebit => "DIRECTDEBIT",
+        TransactionCategory::Cheque => "CHECK",
+        _ if amount < Amount::default() => "DEBIT",
+        _ => "CREDIT",
+    }
+}
+
+/// Returns the ledger balance and its date.
+fn ledger_balance(
+    account: &Account,
+    transactions: &[&Transaction],
+) -> Result<(Amount, DateTime<Utc>)> {
+    if let Some(balance) = account.balance {
+        return Ok((balance, Utc::now()));
+    }
+    transactions
+        .last()
+        .map(|t| (t.account_balance, t.booking_datetime))
+        .ok_or_else(|| {
+            ExportError::InvalidData("No transactions or account balance for OFX".to_string())
+        })
+}
+
+/// Writes the OFX header of the format.
+fn write_header<W: Write>(mut writer: W, format: OfxFormat) -> Result<XmlWriter<W>> {
+    let ofx = match format {
+        OfxFormat::Sgml => {
+            write!(
+                writer,
+                "OFXHEADER:100\r\n\
+                 DATA:OFXSGML\r\n\
+                 VERSION:102\r\n\
+                 SECURITY:NONE\r\n\
+                 ENCODING:UTF-8\r\n\
+                 CHARSET:NONE\r\n\
+                 COMPRESSION:NONE\r\n\
+                 OLDFILEUID:NONE\r\n\
+                 NEWFILEUID:NONE\r\n\
+                 \r\n"
+            )?;
+            XmlWriter::sgml(writer)
+        }
+        OfxFormat::Xml => {
+            let mut xml = XmlWriter::new(writer)?;
+            xml.instruction(
+                "OFX",
+                &[
+                    ("OFXHEADER", "200"),
+                    ("VERSION", "211"),
+                    ("SECURITY", "NONE"),
+                    ("OLDFILEUID", "NONE"),
+                    ("NEWFILEUID", "NONE"),
+                ],
+            )?;
+            xml
+        }
+    };
+    Ok(ofx)
+}
+
+/// Writes successful status.
+fn write_status<W: Write>(ofx: &mut XmlWriter<W>) -> Result<()> {
+    ofx.start("STATUS")?;
+    ofx.text("CODE", "0")?;
+    ofx.text("SEVERITY", "INFO")?;
+    ofx.end()?;
+    Ok(())
+}
+
+/// Writes a single transaction of the statement.
+fn write_transaction<W: Write>(ofx: &mut XmlWriter<W>, transaction: &Transaction) -> Result<()> {
+    let amount = signed_amount(transaction);
+    ofx.start("STMTTRN")?;
+    ofx.text("TRNTYPE", transaction_type(transaction, amount))?;
+    ofx.text("DTPOSTED", &format_datetime(&transaction.booking_datetime))?;
+    ofx.text("DTAVAIL", &format_datetime(&transaction.value_datetaime))?;
+    ofx.text("TRNAMT", &Locale::International.format_amount(amount))?;
+    ofx.text("FITID", &transaction.transaction_id)?;
+    if let Some(reference) = transaction.reference.as_deref().filter(|r| !r.is_empty()) {
+        ofx.text("REFNUM", &truncate(reference, MAX_REFNUM_LENGTH))?;
+    }
+    if let Some(party) = transaction.counterparty() {
+        if !party.account_name.is_empty() {
+            ofx.text("NAME", &truncate(&party.account_name, MAX_NAME_LENGTH))?;
+        }
+    }
+    if let Some(message) = transaction.message.as_deref().filter(|m| !m.is_empty()) {
+        ofx.text("MEMO", &truncate(message, MAX_MEMO_LENGTH))?;
+    }
+    ofx.end()?;
+    Ok(())
+}
+
+/// Writes OFX bank statement of the account with the transactions.
+///
+/// Transactions are written in booking order and the statement period
+/// covers their booking dates. The ledger balance is the current balance
+/// of the account, or the balance after the latest transaction if the
+/// account has no balance. The bank is identified by its BIC without the
+/// branch code, which fits the nine characters allowed for BANKID.
+pub fn write_statement<W: Write>(
+    writer: W,
+    account: &Account,
+    transactions: &[Transaction],
+    format: OfxFormat,
+) -> Result<()> {
+    let mut entries: Vec<&Transaction> = transactions.iter().collect();
+    sort_by_booking(&mut entries);
+    let (balance, balance_datetime) = ledger_balance(account, &entries)?;
+    let now = Utc::now();
+    let start = entries.first().map_or(now, |t| t.booking_datetime);
+    let end = entries.last().map_or(now, |t| t.booking_datetime);
+
+    let mut ofx = write_header(writer, format)?;
+    ofx.start("OFX")?;
+    ofx.start("SIGNONMSGSRSV1")?;
+    ofx.start("SONRS")?;
+    write_status(&mut ofx)?;
+    ofx.text("DTSERVER", &format_datetime(&now))?;
+    ofx.text("LANGUAGE", "FIN")?;
+    ofx.end()?;
+    ofx.end()?;
+
+    ofx.start("BANKMSGSRSV1")?;
+    ofx.start("STMTTRNRS")?;
+    ofx.text("TRNUID", "0")?;
+    write_status(&mut ofx)?;
+    ofx.start("STMTRS")?;
+    ofx.text("CURDEF", &account.currency)?;
+    ofx.start("BANKACCTFROM")?;
+    let bank_id = match account.resolved_bic() {
+        Some(bic) => bic.without_branch().to_string(),
+        None => truncate(&account.servicer_identifier, MAX_BANKID_LENGTH),
+    };
+    ofx.text("BANKID", &bank_id)?;
+    let account_id = match account.iban() {
+        Some(iban) => iban.electronic().to_string(),
+        None => account.identifier.clone(),
+    };
+    ofx.text("ACCTID", &account_id)?;
+    ofx.text("ACCTTYPE", "CHECKING")?;
+    ofx.end()?;
+
+    ofx.start("BANKTRANLIST")?;
+    ofx.text("DTSTART", &format_datetime(&start))?;
+    ofx.text("DTEND", &format_datetime(&end))?;
+    for transaction in entries.iter() {
+        write_transaction(&mut ofx, transaction)?;
+    }
+    ofx.end()?;
+
+    ofx.start("LEDGERBAL")?;
+    ofx.text("BALAMT", &Locale::International.format_amount(balance))?;
+    ofx.text("DTASOF", &format_datetime(&balance_datetime))?;
+    ofx.end()?;
+    ofx.finish()?;
+    Ok(())
+}
+
+/// Returns OFX bank statement of the account as string.
+pub fn to_string(
+    account: &Account,
+    transactions: &[Transaction],
+    format: OfxFormat,
+) -> Result<String> {
+    let mut output = Vec::new();
+    write_statement(&mut output, account, transactions, format)?;
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
@@ -0,0 +1,90 @@
+//! Export of account statements as QIF.
+//!
+//! The statement starts with an account block carrying the current
+//! balance of the account followed by the transactions as a bank
+//! register. QIF has no unique transaction identifiers, so the
+//! transaction ID is written as the number of the transaction.
+
+use super::{local_date, signed_amount, single_line, sort_by_booking, Locale, Result};
+use crate::model::accounts::{Account, Transaction};
+use crate::model::time;
+use std::io::Write;
+
+/// Writes a single transaction of the register.
+fn write_transaction<W: Write>(
+    writer: &mut W,
+    transaction: &Transaction,
+    locale: Locale,
+) -> Result<()> {
+    let date = local_date(&transaction.booking_datetime);
+    writeln!(writer, "D{}", locale.format_date(date))?;
+    writeln!(
+        writer,
+        "T{}",
+        locale.format_amount(signed_amount(transaction))
+    )?;
+    writeln!(writer, "N{}", single_line(&transaction.transaction_id))?;
+    if let Some(party) = transaction.counterparty() {
+        if !party.account_name.is_empty() {
+            writeln!(writer, "P{}", single_line(&party.account_name))?;
+        }
+    }
+    let memo = transaction
+        .message
+        .as_deref()
+        .filter(|m| !m.is_empty())
+        .or_else(|| transaction.reference.as_deref().filter(|r| !r.is_empty()));
+    if let Some(memo) = memo {
+        writeln!(writer, "M{}", single_line(memo))?;
+    }
+    writeln!(writer, "^")?;
+    Ok(())
+}
+
+/// Writes QIF statement of the account with the transactions.
+///
+/// Transactions are written in booking order. Dates and amounts are
+/// formatted in the locale. The message is used as memo, or the
+/// reference if the transaction has no message.
+pub fn write_statement<W: Write>(
+    mut writer: W,
+    account: &Account,
+    transactions: &[Transaction],
+    locale: Locale,
+) -> Result<()> {
+    let mut entries: Vec<&Transaction> = transactions.iter().collect();
+    sort_by_booking(&mut entries);
+
+    writeln!(writer, "!Account")?;
+    let name = account.nickname.as_deref().unwrap_or(&account.name);
+    writeln!(writer, "N{}", single_line(name))?;
+    writeln!(writer, "TBank")?;
+    let identifier = match account.iban() {
+        Some(iban) => iban.print(),
+        None => account.identifier.clone(),
+    };
+    writeln!(writer, "D{}", single_line(&identifier))?;
+    if let Some(balance) = account.balance {
+        writeln!(writer, "/{}", locale.format_date(time::local_today()))?;
+        writeln!(writer, "${}", locale.format_amount(balance))?;
+    }
+    writeln!(writer, "^")?;
+
+    writeln!(writer, "!Type:Bank")?;
+    for transaction in entries {
+        write_transaction(&mut writer, transaction, locale)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Returns QIF statement of the account as string.
+pub fn to_string(
+    account: &Account,
+    transactions: &[Transaction],
+    locale: Locale,
+) -> Result<String> {
+    let mut output = Vec::new();
+    write_statement(&mut output, account, transactions, locale)?;
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
@@ -0,0 +1,305 @@
+//! Export of account statements in the Finnish TITO format.
+//!
+//! TITO (konekielinen tiliote) is the fixed-width account statement
+//! format of the Federation of Finnish Financial Services. A statement
+//! consists of a T00 basic record, T10 transaction records with T11
+//! message records, T40 balance records for each booking date and a T50
+//! cumulative record for the period. Records are separated by CR LF and
+//! written in ISO-8859-1.
+
+use super::{local_date, signed_amount, Result, Statement};
+use crate::banking::Iban;
+use crate::model::accounts::{Account, Transaction};
+use crate::model::money::Amount;
+use crate::model::time;
+use chrono::NaiveDate;
+use std::io::Write;
+
+/// Version of the format.
+const VERSION: &str = "100";
+
+/// Length of a message line of the T11 record.
+const MESSAGE_LINE_LENGTH: usize = 35;
+
+/// Maximum number of message lines of the T11 record.
+const MAX_MESSAGE_LINES: usize = 12;
+
+/// Separator of the records.
+const LINE_SEPARATOR: &str = "\r\n";
+
+/// Record with fields in fixed positions.
+struct Record {
+    kind: &'static str,
+    fields: String,
+}
+
+/// Implementation of the Record.
+impl Record {
+    /// Creates empty record of the type.
+    fn new(kind: &'static str) -> Self {
+        Record {
+            kind,
+            fields: String::new(),
+        }
+    }
+
+    /// Appends left-aligned text padded with spaces.
+    ///
+    /// Characters missing from ISO-8859-1 are replaced with question marks.
+    fn text(mut self, value: &str, width: usize) -> Self {
+        let mut length = 0;
+        for c in value.chars().take(width) {
+            self.fields.push(match c {
+                '\r' | '\n' | '\t' => ' ',
+                c if (c as u32) > 0xFF => '?',
+                c => c,
+            });
+            length += 1;
+        }
+        for _ in length..width {
+            self.fields.push(' ');
+        }
+        self
+    }
+
+    /// Appends right-aligned number padded with zeros.
+    fn number(mut self, value: &str, width: usize) -> Self {
+        let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
+        let skip = digits.len().saturating_sub(width);
+        self.fields
+            .push_str(&format!("{:0>width$}", &digits[skip..], width = width));
+        self
+    }
+
+    /// Appends signed amount in cents.
+    fn amount(self, amount: Amount) -> Self {
+        let sign = if amount < Amount::default() { "-" } else { "+" };
+        let cents = format!("{:.2}", amount.abs()).replace('.', "");
+        self.text(sign, 1).number(&cents, 18)
+    }
+
+    /// Appends date as YYMMDD.
+    fn date(self, date: NaiveDate) -> Self {
+        self.number(&date.format("%y%m%d").to_string(), 6)
+    }
+
+    /// Returns the record with its type and length.
+    fn finish(self) -> String {
+        format!(
+            "T{}{:03}{}",
+            self.kind,
+            6 + self.fields.chars().count(),
+            self.fields
+        )
+    }
+}
+
+/// Returns the domestic account number of Finnish accounts.
+fn account_number(identifier: &str, iban: Option<Iban>) -> String {
+    match iban {
+        Some(iban) if iban.country_code() == "FI" => iban.bban().to_string(),
+        _ => identifier.to_string(),
+    }
+}
+
+/// Returns T00 basic record of the statement.
+fn basic_record(statement: &Statement, record_count: usize) -> String {
+    let account = statement.account;
+    let iban = account.iban();
+    Record::new("00")
+        .text(VERSION, 3)
+        .text(&account_number(&account.identifier, iban.clone()), 14)
+        .number("1", 3)
+        .date(statement.from)
+        .date(statement.to)
+        .number(&time::local_now().format("%y%m%d%H%M").to_string(), 10)
+        .text("", 17)
+        .date(statement.from)
+        .amount(statement.opening)
+        .number(&record_count.to_string(), 6)
+        .text(&account.currency, 3)
+        .text(&account.name, 30)
+        .text("", 18)
+        .text("", 35)
+        .text(account.bank().map_or("", |bank| bank.name), 40)
+        .text("", 40)
+        .text("", 30)
+        .text(iban.as_ref().map_or("", |iban| iban.electronic()), 18)
+        .text(
+            &account
+                .resolved_bic()
+                .map(|bic| bic.to_string())
+                .unwrap_or_default(),
+            11,
+        )
+        .text("", 1)
+        .finish()
+}
+
+/// Returns T10 transaction record.
+fn transaction_record(number: usize, transaction: &Transaction) -> String {
+    let amount = signed_amount(transaction);
+    let booking_date = local_date(&transaction.booking_datetime);
+    let counterparty = transaction.counterparty();
+    let reference = transaction
+        .reference_number()
+        .and_then(|r| r.to_national())
+        .map(|r| r.electronic().to_string())
+        .unwrap_or_default();
+    Record::new("10")
+        .number(&number.to_string(), 6)
+        .text(transaction.archive_id.as_deref().unwrap_or(""), 18)
+        .date(booking_date)
+        .date(local_date(&transaction.value_datetaime))
+        .date(booking_date)
+        .text(if amount < Amount::default() { "2" } else { "1" }, 1)
+        .text(transaction.op_transaction_code.as_deref().unwrap_or(""), 3)
+        .text(
+            transaction
+                .op_transaction_code_info()
+                .map_or("", |code| code.finnish),
+            35,
+        )
+        .amount(amount)
+        .text("", 1)
+        .text("", 1)
+        .text(counterparty.map_or("", |p| &p.account_name), 35)
+        .text("", 1)
+        .text(
+            &counterparty
+                .map(|p| account_number(&p.account_identifier, p.iban()))
+                .unwrap_or_default(),
+            14,
+        )
+        .text("", 1)
+        .number(&reference, 20)
+        .text("", 8)
+        .text("", 1)
+        .finish()
+}
+
+/// Returns T11 message record of the transaction.
+///
+/// References which have no national form do not fit in the T10 record
+/// and are written as the first message line.
+fn message_record(transaction: &Transaction) -> Option<String> {
+    let mut lines: Vec<String> = Vec::new();
+    if let Some(reference) = transaction.reference.as_deref().filter(|r| !r.is_empty()) {
+        let national = transaction.reference_number().and_then(|r| r.to_national());
+        if national.is_none() {
+            lines.push(reference.to_string());
+        }
+    }
+    if let Some(message) = transaction.message.as_deref() {
+        let chars: Vec<char> = message.chars().collect();
+        lines.extend(
+            chars
+                .chunks(MESSAGE_LINE_LENGTH)
+                .map(|chunk| chunk.iter().collect()),
+        );
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    let record = lines
+        .iter()
+        .take(MAX_MESSAGE_LINES)
+        .fold(Record::new("11").number("0", 2), |record, line| {
+            record.text(line, MESSAGE_LINE_LENGTH)
+        });
+    Some(record.finish())
+}
+
+/// Returns T40 balance record of the booking date.
+fn balance_record(date: NaiveDate, balance: Amount) -> String {
+    Record::new("40")
+        .date(date)
+        .amount(balance)
+        .amount(balance)
+        .finish()
+}
+
+/// Returns T50 cumulative record of the statement period.
+fn cumulative_record(statement: &Statement) -> String {
+    let (credits, debits): (Vec<Amount>, Vec<Amount>) = statement
+        .entries
+        .iter()
+        .map(|t| signed_amount(t))
+        .partition(|amount| *amount >= Amount::default());
+    let sum = |amounts: &[Amount]| amounts.iter().fold(Amount::default(), |sum, a| sum + *a);
+    Record::new("50")
+        .number("2", 1)
+        .date(statement.to)
+        .number(&credits.len().to_string(), 8)
+        .amount(sum(&credits))
+        .number(&debits.len().to_string(), 8)
+        .amount(sum(&debits))
+        .finish()
+}
+
+/// Returns records of the statement.
+fn records(statement: &Statement) -> Vec<String> {
+    let mut records = Vec::new();
+    for (i, transaction) in statement.entries.iter().enumerate() {
+        records.push(transaction_record(i + 1, transaction));
+        if let Some(record) = message_record(transaction) {
+            records.push(record);
+        }
+        let date = local_date(&transaction.booking_datetime);
+        let last_of_date = match statement.entries.get(i + 1) {
+            Some(next) => local_date(&next.booking_datetime) != date,
+            None => true,
+        };
+        if last_of_date {
+            records.push(balance_record(date, transaction.account_balance));
+        }
+    }
+    if statement.entries.is_empty() {
+        records.push(balance_record(statement.to, statement.closing));
+    }
+    records.push(cumulative_record(statement));
+    records.insert(0, basic_record(statement, records.len() + 1));
+    records
+}
+
+/// Writes TITO statement of the account for the period from the
+/// transactions.
+///
+/// Only transactions booked during the period in Europe/Helsinki are
+/// included. Archive IDs and reference numbers are taken from the
+/// transactions; RF references are converted to national references
+/// where possible.
+pub fn write_statement<W: Write>(
+    mut writer: W,
+    account: &Account,
+    from: NaiveDate,
+    to: NaiveDate,
+    transactions: &[Transaction],
+) -> Result<()> {
+    let statement = Statement::new(account, from, to, transactions)?;
+    for record in records(&statement) {
+        let bytes: Vec<u8> = record.chars().map(|c| c as u32 as u8).collect();
+        writer.write_all(&bytes)?;
+        writer.write_all(LINE_SEPARATOR.as_bytes())?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Returns TITO statement of the account for the period as string.
+///
+/// Unlike write_statement, the string is not encoded in ISO-8859-1.
+pub fn to_string(
+    account: &Account,
+    from: NaiveDate,
+    to: NaiveDate,
+    transactions: &[Transaction],
+) -> Result<String> {
+    let statement = Statement::new(account, from, to, transactions)?;
+    let mut output = String::new();
+    for record in records(&statement) {
+        output.push_str(&record);
+        output.push_str(LINE_SEPARATOR);
+    }
+    Ok(output)
+}
@@ -0,0 +1,163 @@
+//! Minimal writer for indented XML and SGML documents.
+
+use std::io::{self, Write};
+
+/// Indentation of a single nesting level.
+const INDENT: &str = "  ";
+
+/// Escapes text for XML content and attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if (c as u32) < 0x20 && c != '\n' && c != '\r' && c != '\t' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes text for SGML content, which only defines entities for
+/// ampersands and angle brackets.
+pub(crate) fn escape_sgml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            c if (c as u32) < 0x20 && c != '\n' && c != '\r' && c != '\t' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writer keeping track of the open elements.
+pub(crate) struct XmlWriter<W: Write> {
+    writer: W,
+    open: Vec<&'static str>,
+    sgml: bool,
+}
+
+/// Implementation of the XmlWriter.
+impl<W: Write> XmlWriter<W> {
+    /// Creates writer and writes the XML declaration.
+    pub(crate) fn new(mut writer: W) -> io::Result<Self> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        Ok(XmlWriter {
+            writer,
+            open: Vec::new(),
+            sgml: false,
+        })
+    }
+
+    /// Creates writer for SGML, which leaves elements with text content
+    /// without end tags and escapes only ampersands and angle brackets.
+    pub(crate) fn sgml(writer: W) -> Self {
+        XmlWriter {
+            writer,
+            open: Vec::new(),
+            sgml: true,
+        }
+    }
+
+    /// Writes processing instruction with attributes.
+    pub(crate) fn instruction(
+        &mut self,
+        target: &str,
+        attributes: &[(&str, &str)],
+    ) -> io::Result<()> {
+        self.indent()?;
+        writeln!(
+            self.writer,
+            "<?{}{}?>",
+            target,
+            Self::attributes(attributes)
+        )
+    }
+
+    /// Writes indentation of the current level.
+    fn indent(&mut self) -> io::Result<()> {
+        for _ in 0..self.open.len() {
+            self.writer.write_all(INDENT.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Formats attributes of an element.
+    fn attributes(attributes: &[(&str, &str)]) -> String {
+        attributes
+            .iter()
+            .map(|(name, value)| format!(" {}=\"{}\"", name, escape(value)))
+            .collect()
+    }
+
+    /// Opens element with attributes.
+    pub(crate) fn start_with(
+        &mut self,
+        name: &'static str,
+        attributes: &[(&str, &str)],
+    ) -> io::Result<()> {
+        self.indent()?;
+        writeln!(self.writer, "<{}{}>", name, Self::attributes(attributes))?;
+        self.open.push(name);
+        Ok(())
+    }
+
+    /// Opens element.
+    pub(crate) fn start(&mut self, name: &'static str) -> io::Result<()> {
+        self.start_with(name, &[])
+    }
+
+    /// Closes the latest open element.
+    pub(crate) fn end(&mut self) -> io::Result<()> {
+        let name = self.open.pop().expect("Element is open");
+        self.indent()?;
+        writeln!(self.writer, "</{}>", name)
+    }
+
+    /// Writes element with text content and attributes.
+    pub(crate) fn text_with(
+        &mut self,
+        name: &str,
+        attributes: &[(&str, &str)],
+        text: &str,
+    ) -> io::Result<()> {
+        let text = if self.sgml {
+            escape_sgml(text)
+        } else {
+            escape(text)
+        };
+        self.indent()?;
+        write!(
+            self.writer,
+            "<{}{}>{}",
+            name,
+            Self::attributes(attributes),
+            text
+        )?;
+        if !self.sgml {
+            write!(self.writer, "</{}>", name)?;
+        }
+        writeln!(self.writer)
+    }
+
+    /// Writes element with text content.
+    pub(crate) fn text(&mut self, name: &str, text: &str) -> io::Result<()> {
+        self.text_with(name, &[], text)
+    }
+
+    /// Closes all open elements and flushes the writer.
+    pub(crate) fn finish(mut self) -> io::Result<()> {
+        while !self.open.is_empty() {
+            self.end()?;
+        }
+        self.writer.flush()
+    }
+}
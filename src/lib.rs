@@ -14,15 +14,54 @@
 //! the requests additionally need an OAuth2 token for authorization
 //! of the user.
 //!
+//! # Auth
+//!
+//! The auth module helps obtaining the OAuth2 token for production
+//! access using the authorization code flow with PKCE.
+//!
+//! # Transport
+//!
+//! Requests are sent using a transport from the transport module. The
+//! default transport uses reqwest with a pooled HTTP client, but any
+//! implementation of the Transport trait can be used instead.
+//!
+//! # Error
+//!
+//! All API functions return SdkError from the error module on failure.
+//! It separates transport failures, unsuccessful responses and errors
+//! reported by the API from each other.
+//!
+//! # Banking
+//!
+//! The banking module contains validated types for banking identifiers
+//! such as IBAN and BIC, which can be used with the model and in own
+//! payment forms.
+//!
+//! # Export
+//!
+//! The export module writes transactions to formats used by
+//! spreadsheets and accounting software, such as CSV, ISO 20022
+//! camt.053 and Finnish TITO bank statements, OFX and QIF for personal
+//! finance software and journals of ledger, hledger and beancount.
+//!
 //! # Model
 //!
 //! The model contains all necessary structures for REST communication.
 //! Each endpoint has it's own module.
+//!
+//! Monetary amounts use the Amount type of the money module, which is an
+//! exact decimal unless the default `decimal` feature is disabled.
 
+pub mod auth;
+pub mod banking;
+pub mod export;
 pub mod model;
 pub mod options;
 pub use model::*;
 pub mod client;
+pub mod error;
+pub mod transport;
 
 mod apis;
+mod logging;
 mod requests;
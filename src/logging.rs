@@ -0,0 +1,108 @@
+//! Helpers for logging requests and responses without leaking
+//! credentials.
+//!
+//! Values of secret headers and query parameters are masked unless
+//! logging of secrets is explicitly enabled with
+//! Options::set_log_secrets.
+
+use crate::transport::{HttpRequest, HttpResponse};
+use reqwest::header::HeaderMap;
+use reqwest::Url;
+
+/// Replacement for masked values.
+pub(crate) const REDACTED: &str = "<redacted>";
+
+/// Headers whose values contain credentials.
+const SECRET_HEADERS: [&str; 5] = [
+    "authorization",
+    "x-api-key",
+    "cookie",
+    "set-cookie",
+    "proxy-authorization",
+];
+
+/// Query parameters whose values contain credentials.
+const SECRET_QUERY_PARAMS: [&str; 9] = [
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "client_secret",
+    "code",
+    "code_verifier",
+    "api_key",
+    "apikey",
+    "token",
+];
+
+/// Returns true if the header contains credentials.
+fn is_secret_header(name: &str) -> bool {
+    SECRET_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(name))
+}
+
+/// Returns true if the query parameter contains credentials.
+fn is_secret_query_param(name: &str) -> bool {
+    SECRET_QUERY_PARAMS
+        .iter()
+        .any(|p| p.eq_ignore_ascii_case(name))
+}
+
+/// Formats headers with secret values masked.
+pub(crate) fn format_headers(headers: &HeaderMap, show_secrets: bool) -> String {
+    let values: Vec<String> = headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if !show_secrets && is_secret_header(name.as_str()) {
+                REDACTED
+            } else {
+                value.to_str().unwrap_or("<binary>")
+            };
+            format!("{}: {}", name, value)
+        })
+        .collect();
+    format!("{{{}}}", values.join(", "))
+}
+
+/// Formats URL with secret query parameter values masked.
+pub(crate) fn format_url(url: &Url, show_secrets: bool) -> String {
+    if show_secrets || url.query().is_none() {
+        return url.to_string();
+    }
+    let mut redacted = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| {
+            let value = if is_secret_query_param(&name) {
+                REDACTED.to_string()
+            } else {
+                value.into_owned()
+            };
+            (name.into_owned(), value)
+        })
+        .collect();
+    redacted.query_pairs_mut().clear().extend_pairs(pairs);
+    redacted.to_string()
+}
+
+/// Formats request for logging.
+pub(crate) fn format_request(request: &HttpRequest, show_secrets: bool) -> String {
+    format!(
+        "method={} url={} headers={}",
+        request.method,
+        format_url(&request.url, show_secrets),
+        format_headers(&request.headers, show_secrets)
+    )
+}
+
+/// Formats response to the request for logging.
+pub(crate) fn format_response(
+    request: &HttpRequest,
+    response: &HttpResponse,
+    show_secrets: bool,
+) -> String {
+    format!(
+        "status={} url={} headers={}",
+        response.status,
+        format_url(&request.url, show_secrets),
+        format_headers(&response.headers, show_secrets)
+    )
+}
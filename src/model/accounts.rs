@@ -2,7 +2,14 @@
 //! [AccountsV3](https://op-developer.fi/docs/api/3Oo5zCujXGw2SGEi00skug/OP%20Accounts%20V3.0%20API)
 //! API
 
-use chrono::{DateTime, Utc};
+use crate::banking::{
+    op_transaction_code, transaction_category, BankTransactionCode, Bic, FinnishBank, Iban,
+    OpTransactionCode, Reference, TransactionCategory,
+};
+use crate::model::money::{deserialize_amount, deserialize_optional_amount, Amount, Money};
+use crate::model::time::{self, serialize_local_datetime};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Utc};
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
 /// Link inside the results of Accounts API.
@@ -11,6 +18,40 @@ pub struct Link {
     pub href: String,
 }
 
+string_enum! {
+    /// Scheme of an account or servicer identifier.
+    pub enum IdentifierScheme {
+        /// International Bank Account Number.
+        Iban => "IBAN",
+        /// Business Identifier Code of the bank.
+        Bic => "BIC",
+    }
+}
+
+string_enum! {
+    /// Describes whether the transaction is a debit or credit transaction.
+    pub enum CreditDebitIndicator {
+        /// Money was received to the account.
+        Credit => "credit",
+        /// Money was paid from the account.
+        Debit => "debit",
+    }
+}
+
+string_enum! {
+    /// Current status of the transaction.
+    pub enum TransactionStatus {
+        /// Transaction has been authorised.
+        Authorised => "Authorised",
+        /// Transaction is waiting for authorisation.
+        AwaitingAuthorisation => "AwaitingAuthorisation",
+        /// Transaction has been rejected.
+        Rejected => "Rejected",
+        /// Transaction has been revoked.
+        Revoked => "Revoked",
+    }
+}
+
 /// Describes a single Account in response.
 #[derive(Deserialize, Debug)]
 pub struct Account {
@@ -24,23 +65,71 @@ pub struct Account {
     pub nickname: Option<String>,
     /// Current balance of the account. Note: This field will only be returned after the end user
     /// has provided consent to the consuming application.
-    pub balance: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_optional_amount")]
+    pub balance: Option<Amount>,
     /// Code of the currency of the account. All currency codes are ISO 4217-compliant strings.
     pub currency: String,
-    /// "IBAN".
+    /// Scheme of the account identifier, "IBAN".
     #[serde(rename = "identifierScheme")]
-    pub identifier_scheme: String,
+    pub identifier_scheme: IdentifierScheme,
     /// Account identifier. Follows the scheme described by the field identifierScheme.
     pub identifier: String,
     /// Identifier of the scheme used for identifying the servicer.
     #[serde(rename = "servicerScheme")]
-    pub servicer_scheme: String,
+    pub servicer_scheme: IdentifierScheme,
     /// Identifier of the servicing bank. Follows the scheme described by the servicerScheme
     /// parameter.
     #[serde(rename = "servicerIdentifier")]
     pub servicer_identifier: String,
 }
 
+/// Implementation of the Account.
+impl Account {
+    /// Returns balance of the account in the account currency if
+    /// available.
+    pub fn balance_money(&self) -> Option<Money> {
+        self.balance
+            .map(|balance| Money::new(balance, self.currency.clone()))
+    }
+
+    /// Returns IBAN of the account.
+    ///
+    /// Returns None if the identifier scheme is not IBAN or the identifier
+    /// is not a valid IBAN.
+    pub fn iban(&self) -> Option<Iban> {
+        parse_identifier(
+            &self.identifier_scheme,
+            IdentifierScheme::Iban,
+            &self.identifier,
+        )
+    }
+
+    /// Returns BIC of the servicing bank.
+    ///
+    /// Returns None if the servicer scheme is not BIC or the identifier is
+    /// not a valid BIC.
+    pub fn bic(&self) -> Option<Bic> {
+        parse_identifier(
+            &self.servicer_scheme,
+            IdentifierScheme::Bic,
+            &self.servicer_identifier,
+        )
+    }
+
+    /// Returns the Finnish bank of the account based on the IBAN.
+    pub fn bank(&self) -> Option<&'static FinnishBank> {
+        self.iban()?.finnish_bank()
+    }
+
+    /// Returns BIC of the servicing bank.
+    ///
+    /// If the servicer identifier is missing or not a valid BIC, the BIC
+    /// is looked up based on the IBAN of the account.
+    pub fn resolved_bic(&self) -> Option<Bic> {
+        self.bic().or_else(|| self.bank().map(FinnishBank::bic))
+    }
+}
+
 /// Describes a list of Accounts in accounts response.
 #[derive(Deserialize, Debug)]
 pub struct AccountList {
@@ -48,18 +137,24 @@ pub struct AccountList {
 }
 
 /// Optional parameters to fetch transactions.
-#[derive(Serialize, Debug, Default)]
+#[derive(Serialize, Debug, Default, Clone)]
 pub struct TransactionParams {
-    /// ISO 8601-compatible date-time string representing the earliest date-time from which
-    /// transactions will be queried. Timezone must not be set. Set time to to 00:00:00 for
-    /// Date-only queries.
-    #[serde(rename = "fromBookingDateTime")]
-    pub from_booking_datetime: Option<DateTime<Utc>>,
-    /// ISO 8601-compatible date-time string representing the latest date-time up to which
-    /// transactions will be queried. Timezone must not be set. Set time to 00:00:00 for Date-only
-    /// queries.
-    #[serde(rename = "toBookingDateTime")]
-    pub to_booking_datetime: Option<DateTime<Utc>>,
+    /// Local date-time in Europe/Helsinki representing the earliest date-time from which
+    /// transactions will be queried. Serialized as ISO 8601-compatible date-time string without
+    /// timezone. Set time to 00:00:00 for Date-only queries.
+    #[serde(
+        rename = "fromBookingDateTime",
+        serialize_with = "serialize_local_datetime"
+    )]
+    pub from_booking_datetime: Option<NaiveDateTime>,
+    /// Local date-time in Europe/Helsinki representing the latest date-time up to which
+    /// transactions will be queried. Serialized as ISO 8601-compatible date-time string without
+    /// timezone. Set time to 00:00:00 for Date-only queries.
+    #[serde(
+        rename = "toBookingDateTime",
+        serialize_with = "serialize_local_datetime"
+    )]
+    pub to_booking_datetime: Option<NaiveDateTime>,
     /// Number of transactions to be returned per each page.
     #[serde(rename = "pageSize")]
     pub page_size: Option<u32>,
@@ -71,18 +166,47 @@ pub struct TransactionParams {
 
 /// Implementation of the TransactionParams.
 impl TransactionParams {
-    /// Sets fromBookingDateTime parameter.
-    pub fn with_from_booking_datetime(mut self, datetime: DateTime<Utc>) -> Self {
+    /// Sets fromBookingDateTime parameter as local date-time.
+    pub fn with_from_booking_datetime(mut self, datetime: NaiveDateTime) -> Self {
         self.from_booking_datetime = Some(datetime);
         self
     }
 
-    /// Sets toBookingDateTime parameter.
-    pub fn with_to_booking_datetime(mut self, datetime: DateTime<Utc>) -> Self {
+    /// Sets toBookingDateTime parameter as local date-time.
+    pub fn with_to_booking_datetime(mut self, datetime: NaiveDateTime) -> Self {
         self.to_booking_datetime = Some(datetime);
         self
     }
 
+    /// Sets fromBookingDateTime parameter for a Date-only query.
+    pub fn with_from_booking_date(self, date: NaiveDate) -> Self {
+        self.with_from_booking_datetime(time::start_of_day(date))
+    }
+
+    /// Sets toBookingDateTime parameter for a Date-only query.
+    pub fn with_to_booking_date(self, date: NaiveDate) -> Self {
+        self.with_to_booking_datetime(time::start_of_day(date))
+    }
+
+    /// Sets booking date parameters to cover the whole calendar month of
+    /// the date in Europe/Helsinki.
+    ///
+    /// The end of the last representable month is left open.
+    pub fn with_booking_month(self, date: NaiveDate) -> Self {
+        match time::month_of(date) {
+            Some((first, last)) => self
+                .with_from_booking_date(first)
+                .with_to_booking_date(last),
+            None => self.with_from_booking_date(date.with_day(1).unwrap_or(date)),
+        }
+    }
+
+    /// Returns parameters for the current calendar month in
+    /// Europe/Helsinki.
+    pub fn current_month() -> Self {
+        TransactionParams::default().with_booking_month(time::local_today())
+    }
+
     /// Sets pageSize parameter.
     pub fn with_page_size(mut self, page_size: u32) -> Self {
         self.page_size = Some(page_size);
@@ -96,12 +220,77 @@ impl TransactionParams {
     }
 }
 
+/// Limits for fetching transactions page by page.
+///
+/// By default all pages are fetched.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PagingLimits {
+    /// Maximum number of transactions returned.
+    pub max_items: Option<usize>,
+    /// Maximum number of pages fetched.
+    pub max_pages: Option<usize>,
+}
+
+/// Implementation of the PagingLimits.
+impl PagingLimits {
+    /// Sets maximum number of transactions returned.
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Sets maximum number of pages fetched.
+    pub fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = Some(max_pages);
+        self
+    }
+}
+
+/// Default length of a single window when fetching transaction history.
+const DEFAULT_HISTORY_WINDOW_DAYS: i64 = 90;
+
+/// Options for fetching transaction history of a date range.
+///
+/// The range is split into windows of given length which are fetched
+/// one after another, page by page.
+#[derive(Debug, Clone)]
+pub struct HistoryOptions {
+    /// Length of a single window. Defaults to 90 days.
+    pub window: Duration,
+    /// Number of transactions to be returned per each page.
+    pub page_size: Option<u32>,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        HistoryOptions {
+            window: Duration::days(DEFAULT_HISTORY_WINDOW_DAYS),
+            page_size: None,
+        }
+    }
+}
+
+/// Implementation of the HistoryOptions.
+impl HistoryOptions {
+    /// Sets length of a single window.
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Sets number of transactions to be returned per each page.
+    pub fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+}
+
 /// Describes a single party in Transaction.
 #[derive(Deserialize, Debug)]
 pub struct TransactionParty {
     /// Account identifier schema.
     #[serde(rename = "accountIdentifierType")]
-    pub account_identifier_type: String,
+    pub account_identifier_type: IdentifierScheme,
     /// Name of the account.
     #[serde(rename = "accountName")]
     pub account_name: String,
@@ -113,7 +302,60 @@ pub struct TransactionParty {
     pub servicer_identifier: String,
     /// Type of the servicerIdentifier, i.e. the scheme of the servicer ID.
     #[serde(rename = "servicerIdentifierType")]
-    pub servicer_identifier_type: String,
+    pub servicer_identifier_type: IdentifierScheme,
+}
+
+/// Implementation of the TransactionParty.
+impl TransactionParty {
+    /// Returns IBAN of the party's bank account.
+    ///
+    /// Returns None if the identifier type is not IBAN or the identifier
+    /// is not a valid IBAN.
+    pub fn iban(&self) -> Option<Iban> {
+        parse_identifier(
+            &self.account_identifier_type,
+            IdentifierScheme::Iban,
+            &self.account_identifier,
+        )
+    }
+
+    /// Returns BIC of the party's servicer.
+    ///
+    /// Returns None if the servicer identifier type is not BIC or the
+    /// identifier is not a valid BIC.
+    pub fn bic(&self) -> Option<Bic> {
+        parse_identifier(
+            &self.servicer_identifier_type,
+            IdentifierScheme::Bic,
+            &self.servicer_identifier,
+        )
+    }
+
+    /// Returns the Finnish bank of the party based on the IBAN.
+    pub fn bank(&self) -> Option<&'static FinnishBank> {
+        self.iban()?.finnish_bank()
+    }
+
+    /// Returns BIC of the party's servicer.
+    ///
+    /// If the servicer identifier is missing or not a valid BIC, the BIC
+    /// is looked up based on the IBAN of the party.
+    pub fn resolved_bic(&self) -> Option<Bic> {
+        self.bic().or_else(|| self.bank().map(FinnishBank::bic))
+    }
+}
+
+/// Parses identifier if it follows the expected scheme.
+fn parse_identifier<T: std::str::FromStr>(
+    scheme: &IdentifierScheme,
+    expected: IdentifierScheme,
+    identifier: &str,
+) -> Option<T> {
+    if *scheme == expected {
+        identifier.parse().ok()
+    } else {
+        None
+    }
 }
 
 /// Describes a single Transaction for Account in transactions response.
@@ -134,16 +376,16 @@ pub struct Transaction {
     pub message: Option<String>,
     /// Amount transferred in the transaction. The value is a string decimal. Debit transactions
     /// are marked with a minus sign.
-    pub amount: String,
+    #[serde(deserialize_with = "deserialize_amount")]
+    pub amount: Amount,
     /// Currency of the transaction.
     pub currency: String,
-    /// Enum(debit, credit)
     /// Describes whether the transaction is a debit of credit transaction.
     #[serde(rename = "creditDebitIndicator")]
-    pub credit_debit_indicator: String,
+    pub credit_debit_indicator: CreditDebitIndicator,
     /// Balance of the account after the transaction.
-    #[serde(rename = "accountBalance")]
-    pub account_balance: String,
+    #[serde(rename = "accountBalance", deserialize_with = "deserialize_amount")]
+    pub account_balance: Amount,
     /// Account information of the creditor. The response body will only contain this field if the
     /// transaction is of type debit, i.e. the counterparty in the transaction is the creditor.
     pub creditor: Option<TransactionParty>,
@@ -157,9 +399,8 @@ pub struct Transaction {
     /// account. ISO 8601-formatted date-time string.
     #[serde(rename = "valueDateTime")]
     pub value_datetaime: DateTime<Utc>,
-    /// Enum(Authorised, AwaitingAuthorisation, Rejected, Revoked)
     /// Current status of the transaction.
-    pub status: Option<String>,
+    pub status: Option<TransactionStatus>,
     /// ISO 20022-compliant transaction code for the transaction.
     #[serde(rename = "isoTransactionCode")]
     pub iso_transaction_code: Option<String>,
@@ -168,6 +409,63 @@ pub struct Transaction {
     pub op_transaction_code: Option<String>,
 }
 
+/// Implementation of the Transaction.
+impl Transaction {
+    /// Returns amount of the transaction in the transaction currency.
+    pub fn amount_money(&self) -> Money {
+        Money::new(self.amount, self.currency.clone())
+    }
+
+    /// Returns balance of the account after the transaction.
+    pub fn balance_money(&self) -> Money {
+        Money::new(self.account_balance, self.currency.clone())
+    }
+
+    /// Returns the counterparty of the transaction.
+    ///
+    /// The counterparty is the creditor of debit transactions and the
+    /// debtor of credit transactions.
+    pub fn counterparty(&self) -> Option<&TransactionParty> {
+        match self.credit_debit_indicator {
+            CreditDebitIndicator::Credit => self.debtor.as_ref().or(self.creditor.as_ref()),
+            _ => self.creditor.as_ref().or(self.debtor.as_ref()),
+        }
+    }
+
+    /// Returns reference number of the transaction.
+    ///
+    /// Returns None if the transaction has no reference or it is not a
+    /// valid national or RF reference.
+    pub fn reference_number(&self) -> Option<Reference> {
+        self.reference.as_deref()?.parse().ok()
+    }
+
+    /// Returns ISO 20022 bank transaction code of the transaction.
+    ///
+    /// Returns None if the transaction has no code or it is not in format
+    /// DOMAIN-FAMILY-SUBFAMILY.
+    pub fn bank_transaction_code(&self) -> Option<BankTransactionCode> {
+        self.iso_transaction_code.as_deref()?.parse().ok()
+    }
+
+    /// Returns description of the OP transaction code of the transaction.
+    ///
+    /// Returns None if the transaction has no code or the code is not in
+    /// the catalog.
+    pub fn op_transaction_code_info(&self) -> Option<&'static OpTransactionCode> {
+        op_transaction_code(self.op_transaction_code.as_deref()?)
+    }
+
+    /// Returns category of the transaction based on the ISO 20022 and OP
+    /// transaction codes.
+    pub fn category(&self) -> TransactionCategory {
+        transaction_category(
+            self.op_transaction_code.as_deref(),
+            self.bank_transaction_code().as_ref(),
+        )
+    }
+}
+
 /// Describes links in the Transactions object in transactions response.
 #[derive(Deserialize, Debug)]
 pub struct TransactionListLinks {
@@ -181,3 +479,21 @@ pub struct TransactionList {
     #[serde(rename = "_links")]
     pub links: TransactionListLinks,
 }
+
+/// Name of the paging token query parameter in the links.
+const PAGING_TOKEN_PARAM: &str = "forwardPagingToken";
+
+/// Implementation of the TransactionList.
+impl TransactionList {
+    /// Returns paging token for the next page if there are more
+    /// transactions available.
+    ///
+    /// The token is extracted from the href of the next link.
+    pub fn next_page_token(&self) -> Option<String> {
+        let href = &self.links.next.as_ref()?.href;
+        let url = Url::parse("http://localhost/").ok()?.join(href).ok()?;
+        url.query_pairs()
+            .find(|(name, _)| name == PAGING_TOKEN_PARAM)
+            .map(|(_, value)| value.into_owned())
+    }
+}
@@ -1,6 +1,7 @@
 //! Models required for
 //! [Funds data V1](https://op-developer.fi/docs/api/5lWcjqy3JY2G2y4UGmS6Yw/Funds-data#operation/getFunds)
 //! API
+use crate::model::money::{deserialize_amount, Amount};
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug)]
@@ -13,8 +14,8 @@ pub struct Funds {
     #[serde(rename = "isinCode")]
     pub isin_code: String,
 
-    #[serde(rename = "unitPrice")]
-    pub unit_price: f32,
+    #[serde(rename = "unitPrice", deserialize_with = "deserialize_amount")]
+    pub unit_price: Amount,
 }
 
 #[derive(Deserialize, Debug)]
@@ -2,6 +2,7 @@
 //! [HoldingsV1](https://op-developer.fi/docs/api/3VZiIRoT2EowKC6yeA0gqQ/HoldingsI)
 //! API
 
+use crate::model::money::{deserialize_amount, Amount};
 use serde::Deserialize;
 
 /// Describes a HoldingsInformation in holdings response.
@@ -32,17 +33,17 @@ pub struct Holdings {
     #[serde(rename = "isinCode")]
     pub isin_code: String,
     /// Market value of the holding
-    #[serde(rename = "marketValue")]
-    pub market_value: f64,
+    #[serde(rename = "marketValue", deserialize_with = "deserialize_amount")]
+    pub market_value: Amount,
     /// List of HoldingsItems
     #[serde(rename = "holdingsItem")]
     pub holdings_item: Vec<HoldingsItem>,
     /// Change of value of the holding
-    #[serde(rename = "changeOfValue")]
-    pub change_of_value: f64,
+    #[serde(rename = "changeOfValue", deserialize_with = "deserialize_amount")]
+    pub change_of_value: Amount,
     /// Subscription value of the holding
-    #[serde(rename = "subscriptionValue")]
-    pub subscription_value: f64,
+    #[serde(rename = "subscriptionValue", deserialize_with = "deserialize_amount")]
+    pub subscription_value: Amount,
     /// Change as percentage of the holding
     #[serde(rename = "changeAsPercentage")]
     pub change_as_percentage: f64,
@@ -54,25 +55,25 @@ pub struct HoldingsItem {
     /// Date of the holdings item
     pub date: String,
     /// Market value of the holdings item
-    #[serde(rename = "marketValue")]
-    pub market_value: f64,
+    #[serde(rename = "marketValue", deserialize_with = "deserialize_amount")]
+    pub market_value: Amount,
     /// Subscription value of the holdings item
-    #[serde(rename = "subscriptionValue")]
-    pub subscription_value: f64,
+    #[serde(rename = "subscriptionValue", deserialize_with = "deserialize_amount")]
+    pub subscription_value: Amount,
 }
 
 /// Describes a SumOfAllHoldings in holdings response.
 #[derive(Deserialize, Debug)]
 pub struct SumOfAllHoldings {
     /// Market value of all holdings
-    #[serde(rename = "marketValue")]
-    pub market_value: f64,
+    #[serde(rename = "marketValue", deserialize_with = "deserialize_amount")]
+    pub market_value: Amount,
     /// Change of value of all holdings
-    #[serde(rename = "changeOfValue")]
-    pub change_of_value: f64,
+    #[serde(rename = "changeOfValue", deserialize_with = "deserialize_amount")]
+    pub change_of_value: Amount,
     /// Subscription value of all holdings
-    #[serde(rename = "subscriptionValue")]
-    pub subscription_value: f64,
+    #[serde(rename = "subscriptionValue", deserialize_with = "deserialize_amount")]
+    pub subscription_value: Amount,
     /// Change as percentage of all holdings
     #[serde(rename = "changeAsPercentage")]
     pub change_as_percentage: f64,
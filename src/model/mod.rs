@@ -1,6 +1,71 @@
 //! This module contains data structures for different APIs
 //! defined in [OP API](https://op-developer.fi/docs).
 
+/// Defines an enum for a string field with fixed set of known values.
+///
+/// Values not known by the SDK are kept in the Unknown variant so new
+/// values added to the API do not break deserialization.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+        #[serde(from = "String", into = "String")]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+            /// Value not known by the SDK.
+            Unknown(String),
+        }
+
+        #[doc = concat!("Implementation of the ", stringify!($name), ".")]
+        impl $name {
+            /// Returns the value as used in the API.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)+
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                $(if value.eq_ignore_ascii_case($value) {
+                    return $name::$variant;
+                })+
+                $name::Unknown(value)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                $name::from(value.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                match value {
+                    $name::Unknown(value) => value,
+                    value => value.as_str().to_string(),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
 pub mod accounts;
 pub mod funds;
 pub mod holdings;
+pub mod money;
+pub mod time;
@@ -0,0 +1,195 @@
+//! Monetary amounts used by the models.
+//!
+//! By default amounts are exact decimals from the
+//! [rust_decimal](https://docs.rs/rust_decimal) crate so balances and
+//! sums do not suffer from float rounding. When the default `decimal`
+//! feature is disabled amounts are represented as f64, which may lose
+//! precision.
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Numeric type of the monetary amounts.
+#[cfg(feature = "decimal")]
+pub type Amount = rust_decimal::Decimal;
+
+/// Numeric type of the monetary amounts.
+#[cfg(not(feature = "decimal"))]
+pub type Amount = f64;
+
+/// Error in arithmetic between Money values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    /// Values have different currencies.
+    CurrencyMismatch {
+        /// Currency of the left-hand side value.
+        expected: String,
+        /// Currency of the right-hand side value.
+        found: String,
+    },
+    /// Result of the operation does not fit in the Amount.
+    Overflow,
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyError::CurrencyMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Currency mismatch: expected {}, found {}",
+                    expected, found
+                )
+            }
+            MoneyError::Overflow => write!(f, "Arithmetic overflow"),
+        }
+    }
+}
+
+impl std::error::Error for MoneyError {}
+
+/// Amount of money in a currency.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Money {
+    /// Amount of money. Negative for debits.
+    #[serde(deserialize_with = "deserialize_amount")]
+    pub amount: Amount,
+    /// Code of the currency. All currency codes are ISO 4217-compliant strings.
+    pub currency: String,
+}
+
+/// Implementation of the Money.
+impl Money {
+    /// Creates new Money with amount and currency.
+    pub fn new(amount: Amount, currency: String) -> Money {
+        Money { amount, currency }
+    }
+
+    /// Returns zero amount in the currency.
+    pub fn zero(currency: String) -> Money {
+        Money::new(Amount::default(), currency)
+    }
+
+    /// Returns true if the amount is negative.
+    pub fn is_negative(&self) -> bool {
+        self.amount < Amount::default()
+    }
+
+    /// Returns the amount with opposite sign.
+    pub fn negate(&self) -> Money {
+        Money::new(-self.amount, self.currency.clone())
+    }
+
+    /// Adds other to this value.
+    ///
+    /// Fails if the currencies differ or the result overflows.
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.check_currency(other)?;
+        let amount = checked_add(self.amount, other.amount).ok_or(MoneyError::Overflow)?;
+        Ok(Money::new(amount, self.currency.clone()))
+    }
+
+    /// Subtracts other from this value.
+    ///
+    /// Fails if the currencies differ or the result overflows.
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.checked_add(&other.negate())
+    }
+
+    /// Returns sum of the values in the currency.
+    ///
+    /// Fails if any of the values is in another currency.
+    pub fn sum<'a, I>(currency: String, values: I) -> Result<Money, MoneyError>
+    where
+        I: IntoIterator<Item = &'a Money>,
+    {
+        values
+            .into_iter()
+            .try_fold(Money::zero(currency), |sum, value| sum.checked_add(value))
+    }
+
+    /// Returns error if other is in different currency.
+    fn check_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency.eq_ignore_ascii_case(&other.currency) {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch {
+                expected: self.currency.clone(),
+                found: other.currency.clone(),
+            })
+        }
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+/// Adds amounts returning None on overflow.
+#[cfg(feature = "decimal")]
+fn checked_add(a: Amount, b: Amount) -> Option<Amount> {
+    a.checked_add(b)
+}
+
+/// Adds amounts returning None on overflow.
+#[cfg(not(feature = "decimal"))]
+fn checked_add(a: Amount, b: Amount) -> Option<Amount> {
+    Some(a + b).filter(|sum| sum.is_finite())
+}
+
+/// Parses amount from a decimal string.
+pub fn parse_amount(value: &str) -> Option<Amount> {
+    Amount::from_str(value.trim()).ok()
+}
+
+/// Visitor accepting amounts as JSON numbers or decimal strings.
+struct AmountVisitor;
+
+impl<'de> Visitor<'de> for AmountVisitor {
+    type Value = Amount;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a decimal number or string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Amount, E> {
+        parse_amount(value).ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Amount, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Amount, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Amount, E> {
+        self.visit_str(&value.to_string())
+    }
+}
+
+/// Deserializes amount from a JSON number or decimal string.
+pub(crate) fn deserialize_amount<'de, D>(deserializer: D) -> Result<Amount, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(AmountVisitor)
+}
+
+/// Deserializes optional amount from a JSON number or decimal string.
+pub(crate) fn deserialize_optional_amount<'de, D>(
+    deserializer: D,
+) -> Result<Option<Amount>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Wrapper(#[serde(deserialize_with = "deserialize_amount")] Amount);
+
+    Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|w| w.0))
+}
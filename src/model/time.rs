@@ -0,0 +1,64 @@
+//! Helpers for the local time used by the APIs.
+//!
+//! Date filters of the APIs are given as local date-times in the
+//! Europe/Helsinki timezone without timezone information.
+
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use serde::Serializer;
+
+/// Timezone of the local date-times used by the APIs.
+pub const TIMEZONE: Tz = chrono_tz::Europe::Helsinki;
+
+/// Format of the local date-times in the requests.
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Converts UTC date-time to local date-time.
+pub fn to_local(datetime: &DateTime<Utc>) -> NaiveDateTime {
+    datetime.with_timezone(&TIMEZONE).naive_local()
+}
+
+/// Returns current local date-time.
+pub fn local_now() -> NaiveDateTime {
+    to_local(&Utc::now())
+}
+
+/// Returns current local date.
+pub fn local_today() -> NaiveDate {
+    local_now().date()
+}
+
+/// Returns local midnight starting the date.
+pub fn start_of_day(date: NaiveDate) -> NaiveDateTime {
+    date.and_time(NaiveTime::MIN)
+}
+
+/// Returns the last second of the date, or None if it can not be
+/// represented.
+pub fn end_of_day(date: NaiveDate) -> Option<NaiveDateTime> {
+    start_of_day(date).checked_add_signed(Duration::days(1) - Duration::seconds(1))
+}
+
+/// Returns first and last day of the calendar month of the date.
+///
+/// Returns None for the last representable month, as the first day of
+/// the following month can not be represented.
+pub fn month_of(date: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+    let first = date.with_day(1)?;
+    let last = first.checked_add_months(Months::new(1))?.pred_opt()?;
+    Some((first, last))
+}
+
+/// Serializes optional local date-time without timezone.
+pub(crate) fn serialize_local_datetime<S>(
+    datetime: &Option<NaiveDateTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match datetime {
+        Some(datetime) => serializer.collect_str(&datetime.format(DATETIME_FORMAT)),
+        None => serializer.serialize_none(),
+    }
+}
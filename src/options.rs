@@ -1,36 +1,377 @@
 //! This module contains Options for the clients needed
 //! to make requests to [OP API](https://op-developer.fi).
 
-use std::sync::{Arc, RwLock};
+use crate::apis;
+use crate::auth::TokenProvider;
+use crate::error::Result;
+use crate::transport::{ReqwestTransport, Transport};
+use rand::Rng;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
 
-/// Inner options values inside the RwLock.
-#[derive(Default, Clone)]
-struct OptionsInner {
+/// Options for the default HTTP transport shared by all API clients.
+///
+/// All clients using the same Options share a single connection pool,
+/// so TLS handshakes are done only once per connection instead of once
+/// per request.
+#[derive(Debug, Clone)]
+pub struct HttpOptions {
+    /// Maximum number of idle connections kept per host.
+    pub pool_max_idle_per_host: usize,
+    /// How long idle connections are kept in the pool. None keeps
+    /// them open indefinitely.
+    pub pool_idle_timeout: Option<Duration>,
+    /// TCP keep-alive interval for the pooled connections.
+    pub tcp_keepalive: Option<Duration>,
+    /// Uses HTTP/2 without negotiation when enabled.
+    pub http2_prior_knowledge: bool,
+    /// Total timeout for a single request.
+    pub timeout: Option<Duration>,
+    /// Timeout for establishing new connections.
+    pub connect_timeout: Option<Duration>,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        HttpOptions {
+            pool_max_idle_per_host: usize::MAX,
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            tcp_keepalive: None,
+            http2_prior_knowledge: false,
+            timeout: None,
+            connect_timeout: None,
+        }
+    }
+}
+
+/// Implementation of the HttpOptions.
+impl HttpOptions {
+    /// Sets maximum number of idle connections per host.
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = max;
+        self
+    }
+
+    /// Sets timeout for idle connections in the pool.
+    pub fn with_pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.pool_idle_timeout = timeout;
+        self
+    }
+
+    /// Sets TCP keep-alive interval.
+    pub fn with_tcp_keepalive(mut self, keepalive: Option<Duration>) -> Self {
+        self.tcp_keepalive = keepalive;
+        self
+    }
+
+    /// Enables or disables HTTP/2 prior knowledge.
+    pub fn with_http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.http2_prior_knowledge = enabled;
+        self
+    }
+
+    /// Sets total timeout for a single request.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets timeout for establishing new connections.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+}
+
+/// Retry policy applied to all API requests.
+///
+/// Requests are retried when the API responds with one of the retryable
+/// statuses or when the connection fails or times out. The delay between
+/// attempts grows exponentially and is randomized with jitter so that
+/// multiple clients do not retry at the same time. If the response
+/// contains Retry-After header, its value is used instead.
+#[derive(Debug, Clone)]
+pub struct RetryOptions {
+    /// Maximum number of attempts including the first one. Value of 1
+    /// disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper limit for the delay between attempts.
+    pub max_backoff: Duration,
+    /// Factor the delay is multiplied with after each attempt.
+    pub multiplier: f64,
+    /// Portion of the delay randomized between 0.0 and 1.0.
+    pub jitter: f64,
+    /// HTTP statuses that are retried.
+    pub retryable_statuses: Vec<u16>,
+    /// Whether Retry-After header of the response is respected.
+    pub respect_retry_after: bool,
+    /// Upper limit for the delay requested with Retry-After header.
+    pub max_retry_after: Duration,
+    /// Whether connection errors and timeouts are retried.
+    pub retry_transport_errors: bool,
+}
+
+impl Default for RetryOptions {
+    fn default() -> Self {
+        RetryOptions {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.5,
+            retryable_statuses: vec![429, 502, 503, 504],
+            respect_retry_after: true,
+            max_retry_after: Duration::from_secs(60),
+            retry_transport_errors: true,
+        }
+    }
+}
+
+/// Implementation of the RetryOptions.
+impl RetryOptions {
+    /// Creates retry options that never retry requests.
+    pub fn disabled() -> Self {
+        RetryOptions::default().with_max_attempts(1)
+    }
+
+    /// Sets maximum number of attempts.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets delay before the first retry.
+    pub fn with_initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Sets upper limit for the delay between attempts.
+    pub fn with_max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Sets backoff multiplier.
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets portion of the delay that is randomized.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets HTTP statuses that are retried.
+    pub fn with_retryable_statuses(mut self, statuses: Vec<u16>) -> Self {
+        self.retryable_statuses = statuses;
+        self
+    }
+
+    /// Sets whether Retry-After header is respected.
+    pub fn with_respect_retry_after(mut self, respect: bool) -> Self {
+        self.respect_retry_after = respect;
+        self
+    }
+
+    /// Sets upper limit for the delay requested with Retry-After header.
+    pub fn with_max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after = max;
+        self
+    }
+
+    /// Sets whether connection errors and timeouts are retried.
+    pub fn with_retry_transport_errors(mut self, retry: bool) -> Self {
+        self.retry_transport_errors = retry;
+        self
+    }
+
+    /// Returns true if response with given status should be retried.
+    pub fn is_retryable_status(&self, status: u16) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    /// Returns delay to wait after given failed attempt, starting from 1.
+    ///
+    /// The delay is exponential backoff capped to max_backoff of which
+    /// jitter portion is randomized. Negative multipliers are treated as
+    /// zero and jitter which is not a number as no jitter.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let multiplier = self.multiplier.max(0.0);
+        let delay = self.initial_backoff.as_secs_f64() * multiplier.powi(exponent);
+        let delay = delay.min(self.max_backoff.as_secs_f64()).max(0.0);
+        let jitter = if self.jitter.is_nan() {
+            0.0
+        } else {
+            self.jitter.clamp(0.0, 1.0)
+        };
+        let random: f64 = rand::thread_rng().gen();
+        Duration::try_from_secs_f64(delay * (1.0 - jitter * random)).unwrap_or(self.max_backoff)
+    }
+}
+
+/// APIs available in the clients.
+///
+/// Each API has its own version which is used to construct the request
+/// URLs. The defaults follow the versions the clients are implemented
+/// against and can be overridden per API with Options::set_api_version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Api {
+    /// AccountsV3 API.
+    Accounts,
+    /// Funds data V1 API.
+    Funds,
+    /// HoldingsV1 API.
+    Holdings,
+}
+
+/// Implementation of the Api.
+impl Api {
+    /// Returns version the client for this API is implemented against.
+    pub fn default_version(self) -> &'static str {
+        match self {
+            Api::Accounts => apis::accounts::DEFAULT_VERSION,
+            Api::Funds => apis::funds::DEFAULT_VERSION,
+            Api::Holdings => apis::holdings::DEFAULT_VERSION,
+        }
+    }
+}
+
+/// Implement functionality to display Api.
+impl fmt::Display for Api {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Api::Accounts => "Accounts",
+            Api::Funds => "Funds",
+            Api::Holdings => "Holdings",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Immutable snapshot of the options.
+///
+/// Each request takes a single snapshot when it starts and uses it for
+/// all of its configuration, so concurrent changes to the Options never
+/// mix for example an old API key with a new authorization within one
+/// request. Changes to the Options are visible in the next snapshot.
+#[derive(Clone)]
+pub struct OptionsSnapshot {
     api_key: String,
     authorization: String,
-    version: String,
+    api_versions: HashMap<Api, String>,
     base_url: String,
+    http_options: HttpOptions,
+    transport: Arc<dyn Transport>,
+    token_provider: Option<Arc<dyn TokenProvider>>,
+    retry_options: RetryOptions,
+    log_secrets: bool,
+}
+
+impl Default for OptionsSnapshot {
+    fn default() -> Self {
+        OptionsSnapshot {
+            api_key: String::default(),
+            authorization: String::default(),
+            api_versions: HashMap::new(),
+            base_url: String::default(),
+            http_options: HttpOptions::default(),
+            transport: Arc::new(ReqwestTransport::default()),
+            token_provider: None,
+            retry_options: RetryOptions::default(),
+            log_secrets: false,
+        }
+    }
+}
+
+/// Implementation of the OptionsSnapshot.
+impl OptionsSnapshot {
+    /// Returns base URL for requests.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns API key for requests.
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    /// Returns static authorization for requests.
+    pub fn authorization(&self) -> &str {
+        &self.authorization
+    }
+
+    /// Returns provider for the access tokens if set.
+    pub fn token_provider(&self) -> Option<Arc<dyn TokenProvider>> {
+        self.token_provider.clone()
+    }
+
+    /// Returns version of the API for requests.
+    pub fn api_version(&self, api: Api) -> &str {
+        match self.api_versions.get(&api) {
+            Some(version) => version,
+            None => api.default_version(),
+        }
+    }
+
+    /// Returns options for the default HTTP transport.
+    pub fn http_options(&self) -> &HttpOptions {
+        &self.http_options
+    }
+
+    /// Returns transport for requests.
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        self.transport.clone()
+    }
+
+    /// Returns retry policy for requests.
+    pub fn retry_options(&self) -> &RetryOptions {
+        &self.retry_options
+    }
+
+    /// Returns whether credentials are shown in debug logging.
+    pub fn log_secrets(&self) -> bool {
+        self.log_secrets
+    }
+
+    /// Returns copy of the snapshot without the token provider.
+    pub(crate) fn without_token_provider(&self) -> OptionsSnapshot {
+        OptionsSnapshot {
+            token_provider: None,
+            ..self.clone()
+        }
+    }
 }
 
 /// Struct containing needed options for API clients.
+///
+/// The options are stored as an immutable snapshot which is replaced
+/// atomically on every change. Reading or changing the options never
+/// panics, even if another thread panicked while changing them.
 #[derive(Default)]
 pub struct Options {
-    inner: RwLock<OptionsInner>,
+    inner: RwLock<Arc<OptionsSnapshot>>,
 }
 
 impl Options {
     /// Creates new Options struct for production access.
     ///
     /// Keep in mind that authorization must be feched from the
-    /// OAuth and passed here without 'Bearer' included.
+    /// OAuth and passed here without 'Bearer' included. See the auth
+    /// module for obtaining it with the authorization code flow.
     pub fn new(api_key: String, authorization: String) -> Arc<Options> {
-        Arc::new(Options {
-            inner: RwLock::new(OptionsInner {
-                api_key,
-                authorization,
-                version: String::from("v1"),
-                base_url: String::from("https://prod.apis.op-palvelut.fi/"),
-            }),
+        Options::from_snapshot(OptionsSnapshot {
+            api_key,
+            authorization,
+            base_url: String::from("https://prod.apis.op-palvelut.fi/"),
+            ..OptionsSnapshot::default()
         })
     }
 
@@ -39,21 +380,45 @@ impl Options {
     /// Authorization for this is not required as it uses one of the
     /// predefined authorization keys in https://op-developer.fi/docs
     pub fn new_dev(api_key: String) -> Arc<Options> {
+        Options::from_snapshot(OptionsSnapshot {
+            api_key,
+            authorization: String::from("b6910384440ce06f495976f96a162e2ab1bafbb4"),
+            base_url: String::from("https://sandbox.apis.op-palvelut.fi/"),
+            ..OptionsSnapshot::default()
+        })
+    }
+
+    /// Creates new Options from the snapshot.
+    fn from_snapshot(snapshot: OptionsSnapshot) -> Arc<Options> {
         Arc::new(Options {
-            inner: RwLock::new(OptionsInner {
-                api_key,
-                authorization: String::from("b6910384440ce06f495976f96a162e2ab1bafbb4"),
-                version: String::from("v1"),
-                base_url: String::from("https://sandbox.apis.op-palvelut.fi/"),
-            }),
+            inner: RwLock::new(Arc::new(snapshot)),
         })
     }
 
+    /// Returns current snapshot of all options.
+    ///
+    /// The snapshot does not change even if the options are changed
+    /// after taking it.
+    pub fn snapshot(&self) -> Arc<OptionsSnapshot> {
+        self.inner
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replaces the snapshot with an updated copy.
+    fn update<F: FnOnce(&mut OptionsSnapshot)>(&self, f: F) {
+        let mut inner = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        let mut snapshot = OptionsSnapshot::clone(&inner);
+        f(&mut snapshot);
+        *inner = Arc::new(snapshot);
+    }
+
     /// Gets base URL for requests.
     ///
     /// This is different for production and sandbox environments.
     pub fn base_url(&self) -> String {
-        self.inner.read().unwrap().base_url.clone()
+        self.snapshot().base_url().to_string()
     }
 
     /// Sets base URL for all API requests.
@@ -61,26 +426,26 @@ impl Options {
     /// This is useful when testing the clients and can be used
     /// together with local test or mock server.
     pub fn set_base_url(&self, base_url: String) {
-        self.inner.write().unwrap().base_url = base_url;
+        self.update(|o| o.base_url = base_url);
     }
 
     /// Gets API key for requests.
     ///
     /// This is used as HTTP header x-api-key.
     pub fn api_key(&self) -> String {
-        self.inner.read().unwrap().api_key.clone()
+        self.snapshot().api_key().to_string()
     }
 
     /// Sets API key for API requests using these options.
     pub fn set_api_key(&self, api_key: String) {
-        self.inner.write().unwrap().api_key = api_key;
+        self.update(|o| o.api_key = api_key);
     }
 
     /// Gets Authorization for requests.
     ///
     /// This is used together with 'Bearer ' in the Authorization HTTP header.
     pub fn authorization(&self) -> String {
-        self.inner.read().unwrap().authorization.clone()
+        self.snapshot().authorization().to_string()
     }
 
     /// Sets authorization header value for API requests using these options.
@@ -89,19 +454,140 @@ impl Options {
     /// authorization key as it will be automatically preprended by the
     /// clients.
     pub fn set_authorization(&self, authorization: String) {
-        self.inner.write().unwrap().authorization = authorization;
+        self.update(|o| o.authorization = authorization);
+    }
+
+    /// Sets API key and authorization at once.
+    ///
+    /// Use this instead of separate setters when rotating credentials so
+    /// that no request uses the new API key with the old authorization.
+    pub fn set_credentials(&self, api_key: String, authorization: String) {
+        self.update(|o| {
+            o.api_key = api_key;
+            o.authorization = authorization;
+        });
     }
 
-    /// Sets API version for requests.
+    /// Sets provider for the access tokens of API requests.
     ///
-    /// Some APIs are on different version (AccountsV3 for example).
-    /// This is information is used to construct the request URL.
+    /// When set, the provider is asked for a valid access token before
+    /// each request instead of using the static authorization. See
+    /// RefreshingTokenProvider in the auth module for refreshing the
+    /// token automatically.
+    pub fn set_token_provider(&self, token_provider: Arc<dyn TokenProvider>) {
+        self.update(|o| o.token_provider = Some(token_provider));
+    }
+
+    /// Removes the token provider so that the static authorization is
+    /// used again.
+    pub fn clear_token_provider(&self) {
+        self.update(|o| o.token_provider = None);
+    }
+
+    /// Returns provider for the access tokens if set.
+    pub fn token_provider(&self) -> Option<Arc<dyn TokenProvider>> {
+        self.snapshot().token_provider()
+    }
+
+    /// Overrides version of a single API for requests.
+    ///
+    /// APIs are on different versions (AccountsV3 and HoldingsV1 for
+    /// example) and each client uses the version it is implemented
+    /// against by default. This information is used to construct the
+    /// request URL.
+    pub fn set_api_version(&self, api: Api, version: String) {
+        self.update(|o| {
+            o.api_versions.insert(api, version);
+        });
+    }
+
+    /// Removes version override of the API so that its default version
+    /// is used again.
+    pub fn reset_api_version(&self, api: Api) {
+        self.update(|o| {
+            o.api_versions.remove(&api);
+        });
+    }
+
+    /// Returns version of the API for requests.
+    ///
+    /// This is the overridden version if set, otherwise the default
+    /// version of the API.
+    pub fn api_version(&self, api: Api) -> String {
+        self.snapshot().api_version(api).to_string()
+    }
+
+    /// Sets version of all APIs for requests.
+    ///
+    /// APIs are on different versions, so this overrides the default
+    /// version of every API with the same version.
+    #[deprecated(note = "APIs are versioned separately, use Options::set_api_version")]
     pub fn set_version(&self, version: String) {
-        self.inner.write().unwrap().version = version;
+        for api in [Api::Accounts, Api::Funds, Api::Holdings] {
+            self.set_api_version(api, version.clone());
+        }
     }
 
-    /// Returns API version for requests.
+    /// Returns version of the AccountsV3 API for requests.
+    #[deprecated(note = "APIs are versioned separately, use Options::api_version")]
     pub fn version(&self) -> String {
-        self.inner.read().unwrap().version.clone()
+        self.api_version(Api::Accounts)
+    }
+
+    /// Sets options for the default HTTP transport.
+    ///
+    /// This replaces the transport with new ReqwestTransport so existing
+    /// pooled connections are dropped and custom transport set with
+    /// set_transport is no longer used. Returns error if the transport
+    /// can not be built with the given options.
+    pub fn set_http_options(&self, http_options: HttpOptions) -> Result<()> {
+        let transport = ReqwestTransport::new(&http_options)?;
+        self.update(|o| {
+            o.http_options = http_options;
+            o.transport = Arc::new(transport);
+        });
+        Ok(())
+    }
+
+    /// Returns options for the default HTTP transport.
+    pub fn http_options(&self) -> HttpOptions {
+        self.snapshot().http_options().clone()
+    }
+
+    /// Sets transport used to send all API requests using these options.
+    ///
+    /// This can be used to replace the default reqwest based transport
+    /// with an in-memory fake for tests or a different HTTP stack.
+    pub fn set_transport(&self, transport: Arc<dyn Transport>) {
+        self.update(|o| o.transport = transport);
+    }
+
+    /// Returns transport shared by all requests using these options.
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        self.snapshot().transport()
+    }
+
+    /// Sets retry policy for all API requests using these options.
+    pub fn set_retry_options(&self, retry_options: RetryOptions) {
+        self.update(|o| o.retry_options = retry_options);
+    }
+
+    /// Returns retry policy for API requests.
+    pub fn retry_options(&self) -> RetryOptions {
+        self.snapshot().retry_options().clone()
+    }
+
+    /// Sets whether credentials are shown in debug logging.
+    ///
+    /// By default API key, authorization token and other secret headers
+    /// and query parameters are masked in the logs. Enabling this should
+    /// only be done for local debugging.
+    pub fn set_log_secrets(&self, log_secrets: bool) {
+        self.update(|o| o.log_secrets = log_secrets);
+    }
+
+    /// Returns whether credentials are shown in debug logging.
+    pub fn log_secrets(&self) -> bool {
+        self.snapshot().log_secrets()
     }
 }
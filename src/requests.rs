@@ -1,88 +1,263 @@
-use crate::options::Options;
+use crate::error::{ErrorResponse, Result, SdkError, TransportError};
+use crate::logging;
+use crate::options::{OptionsSnapshot, RetryOptions};
+use crate::transport::{HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
 use log::debug;
-use reqwest::{Client, RequestBuilder, Response, StatusCode};
-use serde::{Deserialize, Serialize};
-use std::error::Error;
-use std::fmt;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER};
+use reqwest::{Method, Url};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::Duration;
 
 pub struct Requests;
 
-/// Single error from OP API.
-#[derive(Deserialize, Debug, Clone)]
-pub struct ApiError {
-    pub id: String,
-    pub level: String,
-    pub r#type: String,
-    pub message: String,
+/// Constructs URL from base url, API url and query parameters.
+fn get_request_url<T: Serialize>(
+    options: &OptionsSnapshot,
+    url: &str,
+    query: Option<T>,
+) -> Result<Url> {
+    let full_url = format!("{base_url}{url}", base_url = options.base_url(), url = url);
+    let mut request_url = Url::parse(&full_url)
+        .map_err(|e| SdkError::Configuration(format!("Invalid URL {}: {}", full_url, e)))?;
+    if let Some(q) = query {
+        let query = serde_urlencoded::to_string(&q)
+            .map_err(|e| SdkError::Configuration(format!("Invalid query parameters: {}", e)))?;
+        if !query.is_empty() {
+            request_url.set_query(Some(&query));
+        }
+    }
+    Ok(request_url)
 }
 
-/// Container for API errors from OP API.
-#[derive(Deserialize, Debug, Clone)]
-pub struct ApiErrors {
-    pub errors: Vec<ApiError>,
+/// Constructs header value from options value.
+fn header_value(name: &str, value: &str) -> Result<HeaderValue> {
+    HeaderValue::from_str(value)
+        .map_err(|_| SdkError::Configuration(format!("Invalid value for header {}", name)))
 }
 
-/// Implement functionality to display ApiErrors.
-impl fmt::Display for ApiErrors {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "API error occurred, reasons: {:?}", self.errors)
+/// Returns access token for the request from the token provider if
+/// set, otherwise the authorization from the options.
+async fn access_token(options: &OptionsSnapshot) -> Result<String> {
+    match options.token_provider() {
+        Some(provider) => provider.access_token().await,
+        None => Ok(options.authorization().to_string()),
     }
 }
 
-/// Implement std::error::Error for ApiErrors.
-impl Error for ApiErrors {}
+/// Constructs necessary headers for the request.
+fn get_headers(options: &OptionsSnapshot, access_token: &str) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-api-key", header_value("x-api-key", options.api_key())?);
+    headers.insert(
+        AUTHORIZATION,
+        header_value("Authorization", &format!("{} {}", "Bearer", access_token))?,
+    );
+    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+    Ok(headers)
+}
 
-/// Constructs string URL from base url and API url.
-fn get_request_url(options: &Options, url: &str) -> String {
-    format!("{base_url}{url}", base_url = options.base_url(), url = url)
+/// Parses Retry-After header value given either as seconds or HTTP date.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.with_timezone(&Utc) - Utc::now();
+    Some(delay.to_std().unwrap_or_else(|_| Duration::from_secs(0)))
 }
 
-/// Sets necessary headers for the request.
-fn set_headers(options: &Options, builder: RequestBuilder) -> RequestBuilder {
-    builder
-        .header("x-api-key", options.api_key())
-        .header(
-            "Authorization",
-            format!("{} {}", "Bearer", options.authorization()),
-        )
-        .header("Accept", "application/json")
+/// Returns delay before the next attempt if the response should be retried.
+fn retry_delay(retry: &RetryOptions, response: &HttpResponse, attempt: u32) -> Option<Duration> {
+    if attempt >= retry.max_attempts || !retry.is_retryable_status(response.status.as_u16()) {
+        return None;
+    }
+    let retry_after = response
+        .headers
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_retry_after)
+        .filter(|_| retry.respect_retry_after);
+    match retry_after {
+        Some(delay) => Some(delay.min(retry.max_retry_after)),
+        None => Some(retry.backoff(attempt)),
+    }
 }
 
-/// Sets query parameters for the request.
-fn set_query_params<T: Serialize>(query: Option<T>, builder: RequestBuilder) -> RequestBuilder {
-    match query {
-        Some(q) => builder.query(&q),
-        None => builder,
+/// Returns delay before the next attempt if the transport error should be
+/// retried.
+fn retry_delay_for_error(
+    retry: &RetryOptions,
+    error: &TransportError,
+    attempt: u32,
+) -> Option<Duration> {
+    if attempt >= retry.max_attempts
+        || !retry.retry_transport_errors
+        || !(error.is_timeout() || error.is_connect())
+    {
+        return None;
     }
+    Some(retry.backoff(attempt))
 }
 
-/// Checks for possible API errors from the response
-async fn check_errors(response: Response) -> Result<Response, Box<dyn Error>> {
-    match response.status() {
-        StatusCode::OK => Ok(response),
-        _ => {
-            let errors: ApiErrors = response.json().await?;
-            Err(Box::new(errors))
-        }
+/// Decodes JSON body and keeps the raw body in case of failure.
+///
+/// Empty body is decoded as JSON null so that responses without content
+/// can be decoded to Option or unit types.
+fn decode<T: DeserializeOwned>(body: String) -> Result<T> {
+    let json = if body.trim().is_empty() {
+        "null"
+    } else {
+        &body
+    };
+    serde_json::from_str(json).map_err(|source| SdkError::Decode { source, body })
+}
+
+/// Checks for possible API errors from the response and decodes the body.
+///
+/// All 2xx statuses are considered successful.
+fn check_errors<T: DeserializeOwned>(response: HttpResponse) -> Result<T> {
+    let body = response.text();
+    if response.status.is_success() {
+        return decode(body);
     }
+    Err(SdkError::from_response(ErrorResponse::new(
+        response.status,
+        response.headers,
+        body,
+    )))
 }
 
 /// Internal requests functionality to ease client development.
 ///
 /// These functions set up all necessary headers and run the request
-/// asynchronously.
+/// asynchronously. All configuration is read from a single snapshot of
+/// the options taken by the caller.
 impl Requests {
-    /// Performs GET request to API specified with url.
-    pub async fn get<T: Serialize>(
-        options: &Options,
+    /// Performs GET request to API specified with url and decodes the
+    /// response body.
+    ///
+    /// The request is sent using the transport from the options, see
+    /// [HttpOptions](crate::options::HttpOptions) for the default. Failed
+    /// requests are retried according to the retry options. If the API
+    /// rejects the access token from the token provider, the request is
+    /// sent once more with a refreshed token.
+    pub async fn get<T: DeserializeOwned, Q: Serialize>(
+        options: &OptionsSnapshot,
         url: &str,
-        query: Option<T>,
-    ) -> Result<Response, Box<dyn Error>> {
-        let request_url = get_request_url(options, url);
-        let builder = Client::new().get(&request_url);
-        let client = set_headers(options, set_query_params(query, builder));
-        debug!("Sending request: {:?}", client);
-        let response = client.send().await?;
-        Ok(check_errors(response).await?)
+        query: Option<Q>,
+    ) -> Result<T> {
+        let retry = options.retry_options().clone();
+        let token = access_token(options).await?;
+        let request = HttpRequest {
+            method: Method::GET,
+            url: get_request_url(options, url, query)?,
+            headers: get_headers(options, &token)?,
+            body: None,
+        };
+        match Requests::send(options, request.clone(), retry.clone()).await {
+            Err(SdkError::Authentication(response)) => match options.token_provider() {
+                Some(provider) if provider.invalidate(&token).await => {
+                    debug!("Access token was rejected, retrying with refreshed token");
+                    let token = provider.access_token().await?;
+                    let request = HttpRequest {
+                        headers: get_headers(options, &token)?,
+                        ..request
+                    };
+                    Requests::send(options, request, retry).await
+                }
+                _ => Err(SdkError::Authentication(response)),
+            },
+            result => result,
+        }
+    }
+
+    /// Performs POST request with form encoded body to the absolute url
+    /// and decodes the response body.
+    ///
+    /// Only the given headers are sent as the request is not made to the
+    /// API itself, for example to the OAuth token endpoint. The request
+    /// is not retried as POST requests are not idempotent. The body of a
+    /// response that can not be decoded is not kept in the error as it
+    /// may contain tokens.
+    pub async fn post_form<T: DeserializeOwned, F: Serialize>(
+        options: &OptionsSnapshot,
+        url: &str,
+        form: &F,
+        mut headers: HeaderMap,
+    ) -> Result<T> {
+        let url = Url::parse(url)
+            .map_err(|e| SdkError::Configuration(format!("Invalid URL {}: {}", url, e)))?;
+        let body = serde_urlencoded::to_string(form)
+            .map_err(|e| SdkError::Configuration(format!("Invalid form parameters: {}", e)))?;
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        let request = HttpRequest {
+            method: Method::POST,
+            url,
+            headers,
+            body: Some(body.into_bytes()),
+        };
+        Requests::send(options, request, RetryOptions::disabled())
+            .await
+            .map_err(|e| match e {
+                SdkError::Decode { source, .. } => SdkError::Decode {
+                    source,
+                    body: logging::REDACTED.to_string(),
+                },
+                e => e,
+            })
+    }
+
+    /// Sends the request with retries and decodes the response body.
+    async fn send<T: DeserializeOwned>(
+        options: &OptionsSnapshot,
+        request: HttpRequest,
+        retry: RetryOptions,
+    ) -> Result<T> {
+        let transport = options.transport();
+        let log_secrets = options.log_secrets();
+        let mut attempt = 1;
+        loop {
+            debug!(
+                "Sending request: {}",
+                logging::format_request(&request, log_secrets)
+            );
+            let delay = match transport.send(request.clone()).await {
+                Ok(response) => match retry_delay(&retry, &response, attempt) {
+                    Some(delay) => {
+                        debug!("Request failed with status {}", response.status);
+                        delay
+                    }
+                    None => {
+                        debug!(
+                            "Received response: {}",
+                            logging::format_response(&request, &response, log_secrets)
+                        );
+                        return check_errors(response);
+                    }
+                },
+                Err(e) => match retry_delay_for_error(&retry, &e, attempt) {
+                    Some(delay) => {
+                        debug!("Request failed with error {}", e);
+                        delay
+                    }
+                    None => return Err(SdkError::Transport(e)),
+                },
+            };
+            debug!(
+                "Retrying request in {:?} (attempt {}/{})",
+                delay,
+                attempt + 1,
+                retry.max_attempts
+            );
+            tokio::time::delay_for(delay).await;
+            attempt += 1;
+        }
     }
 }
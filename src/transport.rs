@@ -0,0 +1,121 @@
+//! This module contains the HTTP transport used by the clients to
+//! send requests to [OP API](https://op-developer.fi).
+//!
+//! By default the clients use ReqwestTransport. Any implementation of
+//! the Transport trait can be given to the Client instead, for example
+//! an in-memory fake for unit tests or a transport recording all
+//! requests and responses.
+
+use crate::error::{Result, SdkError, TransportError};
+use crate::options::HttpOptions;
+pub use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode, Url};
+
+/// HTTP request to be sent by the transport.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    /// HTTP method of the request.
+    pub method: Method,
+    /// Full URL of the request including query parameters.
+    pub url: Url,
+    /// HTTP headers of the request.
+    pub headers: HeaderMap,
+    /// Body of the request if any.
+    pub body: Option<Vec<u8>>,
+}
+
+/// HTTP response received by the transport.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// HTTP status of the response.
+    pub status: StatusCode,
+    /// HTTP headers of the response.
+    pub headers: HeaderMap,
+    /// Raw body of the response.
+    pub body: Vec<u8>,
+}
+
+/// Implementation of the HttpResponse.
+impl HttpResponse {
+    /// Returns body of the response as text.
+    ///
+    /// Invalid UTF-8 sequences are replaced with the replacement character.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Transport sending HTTP requests for the clients.
+///
+/// Implementations must be thread safe as the same transport is shared
+/// by all clients using the same Options. Use the re-exported
+/// async_trait attribute when implementing this trait.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Sends the request and returns the response.
+    ///
+    /// Unsuccessful HTTP statuses must be returned as responses, errors
+    /// are only for failures in sending the request or receiving the
+    /// response.
+    async fn send(&self, request: HttpRequest)
+        -> std::result::Result<HttpResponse, TransportError>;
+}
+
+/// Transport using reqwest with a pooled HTTP client.
+///
+/// The client is configured with [HttpOptions], which describes how the
+/// connections are pooled.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+/// Implementation of the ReqwestTransport.
+impl ReqwestTransport {
+    /// Creates new transport based on the HTTP options.
+    pub fn new(http_options: &HttpOptions) -> Result<ReqwestTransport> {
+        let mut builder = reqwest::Client::builder()
+            .pool_max_idle_per_host(http_options.pool_max_idle_per_host)
+            .pool_idle_timeout(http_options.pool_idle_timeout)
+            .tcp_keepalive(http_options.tcp_keepalive);
+        if http_options.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(timeout) = http_options.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = http_options.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        let client = builder
+            .build()
+            .map_err(|e| SdkError::Configuration(e.to_string()))?;
+        Ok(ReqwestTransport { client })
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(
+        &self,
+        request: HttpRequest,
+    ) -> std::result::Result<HttpResponse, TransportError> {
+        let mut builder = self
+            .client
+            .request(request.method, request.url)
+            .headers(request.headers);
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+        let response = builder.send().await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?.to_vec();
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
@@ -13,7 +13,6 @@ mod accounts_tests {
     async fn test_accounts() {
         init();
         let options = Options::new_dev(env::var("X_API_KEY").unwrap());
-        options.set_version("v3".to_string());
         let client = Client::new(options);
 
         // First test getting all accounts
@@ -28,9 +27,9 @@ mod accounts_tests {
                 assert_eq!(false, account.account_id.is_empty());
                 assert_eq!(false, account.name.is_empty());
                 assert_eq!(false, account.currency.is_empty());
-                assert_eq!(false, account.identifier_scheme.is_empty());
+                assert_eq!(false, account.identifier_scheme.as_str().is_empty());
                 assert_eq!(false, account.identifier.is_empty());
-                assert_eq!(false, account.servicer_scheme.is_empty());
+                assert_eq!(false, account.servicer_scheme.as_str().is_empty());
                 assert_eq!(false, account.servicer_identifier.is_empty());
                 Some(account)
             }
@@ -76,7 +75,7 @@ mod accounts_tests {
 
         for trans in transactions.transactions.iter() {
             assert_eq!(false, trans.transaction_id.is_empty());
-            assert_eq!(false, trans.amount.is_empty());
+            assert_eq!(trans.currency, trans.amount_money().currency);
         }
     }
 }
@@ -0,0 +1,304 @@
+mod common;
+
+#[cfg(test)]
+mod auth_tests {
+    use super::common::{FakeTransport, MockResponse, MockServer};
+    use chrono::{Duration, Utc};
+    use op_api_sdk::auth::{
+        OAuthClient, OAuthConfig, PkceChallenge, RefreshingTokenProvider, StaticTokenProvider,
+        TokenProvider, TokenSet,
+    };
+    use op_api_sdk::client::Client;
+    use op_api_sdk::error::SdkError;
+    use op_api_sdk::options::Options;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    const REFRESHED: &str = r#"{
+        "access_token": "access-2",
+        "token_type": "Bearer",
+        "expires_in": 3600
+    }"#;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    fn oauth_client(token_url: String) -> OAuthClient {
+        let config = OAuthConfig::new(
+            "client-1".to_string(),
+            "https://app.example/callback".to_string(),
+        )
+        .with_client_secret("secret-1".to_string())
+        .with_scopes(vec!["accounts".to_string(), "openid".to_string()])
+        .with_token_url(token_url);
+        OAuthClient::new(Options::new_dev("key".to_string()), config)
+    }
+
+    #[test]
+    fn test_pkce_challenge() {
+        // Example from RFC 7636, appendix B
+        let pkce =
+            PkceChallenge::from_verifier("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_string());
+        assert_eq!(
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM",
+            pkce.challenge
+        );
+        assert_eq!("S256", pkce.method());
+
+        let random = PkceChallenge::new();
+        assert_eq!(64, random.verifier.len());
+        assert_ne!(random.verifier, PkceChallenge::new().verifier);
+    }
+
+    #[test]
+    fn test_authorization_request() {
+        let client = oauth_client("https://op.example/token".to_string());
+        let request = client.authorization_request().unwrap();
+        let params: HashMap<String, String> = request.url.query_pairs().into_owned().collect();
+
+        assert!(request
+            .url
+            .as_str()
+            .starts_with("https://authorize.op.fi/oauth/authorize?"));
+        assert_eq!("code", params["response_type"]);
+        assert_eq!("client-1", params["client_id"]);
+        assert_eq!("https://app.example/callback", params["redirect_uri"]);
+        assert_eq!("accounts openid", params["scope"]);
+        assert_eq!(request.state, params["state"]);
+        assert_eq!("S256", params["code_challenge_method"]);
+        assert_eq!(
+            PkceChallenge::from_verifier(request.pkce_verifier.clone()).challenge,
+            params["code_challenge"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exchange_code() {
+        init();
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            r#"{
+                "access_token": "access-1",
+                "token_type": "Bearer",
+                "expires_in": 3600,
+                "refresh_token": "refresh-1",
+                "scope": "accounts openid"
+            }"#,
+        )]);
+        let client = oauth_client(format!("{}/oauth/token", server.url()));
+
+        let resp = client.exchange_code("code-1", "verifier-1").await;
+        assert!(resp.is_ok(), "{:?}", resp.err());
+        let tokens = resp.unwrap();
+        assert_eq!("access-1", tokens.access_token);
+        assert_eq!(Some("refresh-1".to_string()), tokens.refresh_token);
+        assert!(!tokens.is_expired());
+        assert!(tokens.expires_within(Duration::hours(2)));
+        assert!(tokens.expires_at.unwrap() > Utc::now() + Duration::minutes(59));
+        assert!(!format!("{:?}", tokens).contains("access-1"));
+
+        let requests = server.requests();
+        assert_eq!(1, requests.len());
+        assert_eq!("POST", requests[0].method);
+        assert_eq!("/oauth/token", requests[0].path);
+        assert_eq!(
+            Some("application/x-www-form-urlencoded".to_string()),
+            requests[0].header("content-type")
+        );
+        let form: HashMap<String, String> = url_form(&requests[0].body);
+        assert_eq!("authorization_code", form["grant_type"]);
+        assert_eq!("code-1", form["code"]);
+        assert_eq!("verifier-1", form["code_verifier"]);
+        assert_eq!("client-1", form["client_id"]);
+        assert_eq!("secret-1", form["client_secret"]);
+        assert_eq!("https://app.example/callback", form["redirect_uri"]);
+    }
+
+    #[tokio::test]
+    async fn test_exchange_code_with_invalid_lifetime() {
+        init();
+        let response = |expires_in: i64| {
+            MockResponse::json(
+                200,
+                &format!(
+                    r#"{{"access_token": "access-1", "token_type": "Bearer", "expires_in": {}}}"#,
+                    expires_in
+                ),
+            )
+        };
+        let server = MockServer::start(vec![response(i64::MAX), response(-3600)]);
+        let client = oauth_client(format!("{}/oauth/token", server.url()));
+
+        let tokens = client.exchange_code("code-1", "verifier-1").await.unwrap();
+        assert_eq!(None, tokens.expires_at);
+        assert!(!tokens.is_expired());
+
+        let tokens = client.exchange_code("code-1", "verifier-1").await.unwrap();
+        assert!(tokens.is_expired());
+    }
+
+    #[tokio::test]
+    async fn test_undecodable_token_response_is_redacted() {
+        init();
+        let server = MockServer::start(vec![MockResponse::json(
+            200,
+            r#"{"access_token": "access-1", "refresh_token": "refresh-1"}"#,
+        )]);
+        let client = oauth_client(format!("{}/oauth/token", server.url()));
+
+        let error = client
+            .exchange_code("code-1", "verifier-1")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, SdkError::Decode { .. }));
+        let message = format!("{} {:?}", error, error);
+        assert!(!message.contains("access-1"));
+        assert!(!message.contains("refresh-1"));
+    }
+
+    #[tokio::test]
+    async fn test_exchange_code_failure() {
+        init();
+        let server = MockServer::start(vec![MockResponse::json(
+            400,
+            r#"{"error": "invalid_grant"}"#,
+        )]);
+        let client = oauth_client(format!("{}/oauth/token", server.url()));
+
+        match client.exchange_code("code-1", "verifier-1").await {
+            Err(SdkError::Http(response)) => {
+                assert_eq!(400, response.status.as_u16());
+                assert!(response.body.contains("invalid_grant"));
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert_eq!(1, server.requests().len());
+    }
+
+    fn tokens(expires_in: i64) -> TokenSet {
+        TokenSet {
+            access_token: "access-1".to_string(),
+            token_type: "Bearer".to_string(),
+            refresh_token: Some("refresh-1".to_string()),
+            expires_at: Some(Utc::now() + Duration::seconds(expires_in)),
+            scope: None,
+            id_token: None,
+        }
+    }
+
+    fn api_client(provider: Arc<dyn TokenProvider>) -> (Client, Arc<FakeTransport>) {
+        let api = FakeTransport::new(vec![]);
+        let options = Options::new("key".to_string(), String::new());
+        options.set_base_url("https://op.example".to_string());
+        options.set_token_provider(provider);
+        (Client::with_transport(options, api.clone()), api)
+    }
+
+    fn authorization(transport: &FakeTransport, index: usize) -> String {
+        transport.requests()[index].headers["authorization"]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_token_is_refreshed_before_expiry() {
+        init();
+        let server = MockServer::start(vec![MockResponse::json(200, REFRESHED)]);
+        let provider = Arc::new(RefreshingTokenProvider::new(
+            oauth_client(format!("{}/oauth/token", server.url())),
+            tokens(10),
+        ));
+        let (client, api) = api_client(provider.clone());
+        api.push(MockResponse::json(200, r#"{"accounts": []}"#));
+
+        assert!(client.accounts().await.is_ok());
+        assert_eq!("Bearer access-2", authorization(&api, 0));
+
+        let form = url_form(&server.requests()[0].body);
+        assert_eq!("refresh_token", form["grant_type"]);
+        assert_eq!("refresh-1", form["refresh_token"]);
+
+        // Refresh token is kept when the endpoint does not return new one
+        let tokens = provider.tokens().await;
+        assert_eq!(Some("refresh-1".to_string()), tokens.refresh_token);
+    }
+
+    #[tokio::test]
+    async fn test_cached_token_is_used_when_refresh_fails() {
+        init();
+        let server = MockServer::start(vec![MockResponse::json(500, "")]);
+        let provider = RefreshingTokenProvider::new(
+            oauth_client(format!("{}/oauth/token", server.url())),
+            tokens(10),
+        );
+        assert_eq!("access-1", provider.access_token().await.unwrap());
+        assert_eq!(1, server.requests().len());
+
+        let server = MockServer::start(vec![MockResponse::json(500, "")]);
+        let provider = RefreshingTokenProvider::new(
+            oauth_client(format!("{}/oauth/token", server.url())),
+            tokens(-10),
+        );
+        assert!(provider.access_token().await.is_err());
+    }
+
+    #[test]
+    fn test_provider_does_not_keep_options_alive() {
+        let options = Options::new("key".to_string(), String::new());
+        let config = OAuthConfig::new(
+            "client-1".to_string(),
+            "https://app.example/callback".to_string(),
+        );
+        let client = OAuthClient::new(options.clone(), config);
+        let provider = RefreshingTokenProvider::new(client, tokens(3600));
+        options.set_token_provider(Arc::new(provider));
+
+        let weak = Arc::downgrade(&options);
+        drop(options);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_token_is_refreshed_after_rejection() {
+        init();
+        let server = MockServer::start(vec![MockResponse::json(200, REFRESHED)]);
+        let provider = RefreshingTokenProvider::new(
+            oauth_client(format!("{}/oauth/token", server.url())),
+            tokens(3600),
+        );
+        let (client, api) = api_client(Arc::new(provider));
+        api.push(MockResponse::json(401, ""));
+        api.push(MockResponse::json(200, r#"{"accounts": []}"#));
+
+        let resp = client.accounts().await;
+        assert!(resp.is_ok(), "{:?}", resp.err());
+        assert_eq!(2, api.requests().len());
+        assert_eq!("Bearer access-1", authorization(&api, 0));
+        assert_eq!("Bearer access-2", authorization(&api, 1));
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_static_token_is_not_retried() {
+        init();
+        let provider = StaticTokenProvider::new("static-1".to_string());
+        let (client, api) = api_client(Arc::new(provider));
+        api.push(MockResponse::json(401, ""));
+        api.push(MockResponse::json(200, r#"{"accounts": []}"#));
+
+        let resp = client.accounts().await;
+        assert!(matches!(resp, Err(SdkError::Authentication(_))));
+        assert_eq!(1, api.requests().len());
+        assert_eq!("Bearer static-1", authorization(&api, 0));
+    }
+
+    fn url_form(body: &str) -> HashMap<String, String> {
+        reqwest::Url::parse(&format!("http://localhost/?{}", body))
+            .unwrap()
+            .query_pairs()
+            .into_owned()
+            .collect()
+    }
+}
@@ -0,0 +1,314 @@
+mod common;
+
+#[cfg(test)]
+mod banking_tests {
+    use super::common::transaction_json;
+    use op_api_sdk::banking::{
+        op_transaction_code, BankTransactionCode, Bic, Iban, Reference, ReferenceKind,
+        TransactionCategory, ValidationError,
+    };
+    use op_api_sdk::model::accounts::Transaction;
+
+    #[test]
+    fn test_iban() {
+        let iban = Iban::parse("fi42 5000 1510 0000 23").unwrap();
+        assert_eq!("FI4250001510000023", iban.electronic());
+        assert_eq!("FI42 5000 1510 0000 23", iban.print());
+        assert_eq!("FI", iban.country_code());
+        assert_eq!("42", iban.check_digits());
+        assert_eq!("50001510000023", iban.bban());
+        assert_eq!(Some("500"), iban.bank_code());
+
+        let iban: Iban = "DE89370400440532013000".parse().unwrap();
+        assert_eq!(Some("37040044"), iban.bank_code());
+        let iban: Iban = "IT60X0542811101000000123456".parse().unwrap();
+        assert_eq!(Some("05428"), iban.bank_code());
+        let iban: Iban = "GB29NWBK60161331926819".parse().unwrap();
+        assert_eq!(Some("NWBK"), iban.bank_code());
+    }
+
+    #[test]
+    fn test_invalid_iban() {
+        assert_eq!(
+            Err(ValidationError::InvalidChecksum),
+            Iban::parse("FI4250001510000024")
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidLength(17)),
+            Iban::parse("FI425000151000002")
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidCharacter('-')),
+            Iban::parse("FI42-5000-1510-0000-23")
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidCharacter('4')),
+            Iban::parse("4I4250001510000023")
+        );
+        assert!(Iban::parse("").is_err());
+    }
+
+    #[test]
+    fn test_iban_from_bban() {
+        let iban = Iban::from_bban("FI", "50001510000023").unwrap();
+        assert_eq!("FI4250001510000023", iban.electronic());
+        let iban = Iban::from_bban("GB", "NWBK60161331926819").unwrap();
+        assert_eq!("GB29NWBK60161331926819", iban.electronic());
+        assert!(Iban::from_bban("F1", "50001510000023").is_err());
+    }
+
+    #[test]
+    fn test_bic() {
+        let bic = Bic::parse("okoyfihh").unwrap();
+        assert_eq!("OKOYFIHH", bic.as_str());
+        assert_eq!("OKOY", bic.bank_code());
+        assert_eq!("FI", bic.country_code());
+        assert_eq!("HH", bic.location_code());
+        assert_eq!(None, bic.branch_code());
+        assert!(bic.is_primary_office());
+
+        let bic: Bic = "DEUTDEFF500".parse().unwrap();
+        assert_eq!(Some("500"), bic.branch_code());
+        assert!(!bic.is_primary_office());
+        assert_eq!("DEUTDEFF", bic.without_branch().as_str());
+
+        assert_eq!(
+            Err(ValidationError::InvalidLength(9)),
+            Bic::parse("OKOYFIHH1")
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidCharacter('1')),
+            Bic::parse("OKOY1IHH")
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        let iban: Iban = serde_json::from_str("\"FI42 5000 1510 0000 23\"").unwrap();
+        assert_eq!(
+            "\"FI4250001510000023\"",
+            serde_json::to_string(&iban).unwrap()
+        );
+        assert!(serde_json::from_str::<Iban>("\"FI4250001510000024\"").is_err());
+        assert!(serde_json::from_str::<Bic>("\"OKOYFIHH\"").is_ok());
+    }
+
+    #[test]
+    fn test_model_accessors() {
+        let json = transaction_json("1", "2020-01-01T10:00:00Z");
+        let transaction: Transaction = serde_json::from_str(&json).unwrap();
+        let creditor = transaction.creditor.unwrap();
+        assert_eq!(
+            Some("FI4250001510000023"),
+            creditor.iban().as_ref().map(Iban::electronic)
+        );
+        assert_eq!(Some("OKOYFIHH"), creditor.bic().as_ref().map(Bic::as_str));
+    }
+
+    fn bank_name(bban: &str) -> Option<&'static str> {
+        let iban = Iban::from_bban("FI", bban).unwrap();
+        iban.finnish_bank().map(|bank| bank.name)
+    }
+
+    #[test]
+    fn test_finnish_bank() {
+        let bank = Iban::parse("FI4250001510000023")
+            .unwrap()
+            .finnish_bank()
+            .unwrap();
+        assert_eq!("OP Ryhmä", bank.name);
+        assert_eq!("OKOYFIHH", bank.bic().as_str());
+
+        assert_eq!(Some("Nordea Pankki"), bank_name("12345600000785"));
+        assert_eq!(Some("Danske Bank"), bank_name("80000010000000"));
+        assert_eq!(Some("Danske Bank"), bank_name("34000010000000"));
+        assert_eq!(Some("Aktia Pankki"), bank_name("40550010000000"));
+        assert_eq!(Some("Säästöpankit"), bank_name("42550010000000"));
+        assert_eq!(Some("Holvi"), bank_name("79950010000000"));
+        assert_eq!(None, bank_name("99950010000000"));
+
+        let iban = Iban::parse("DE89370400440532013000").unwrap();
+        assert_eq!(None, iban.finnish_bank());
+    }
+
+    #[test]
+    fn test_missing_bic_is_resolved() {
+        let json = transaction_json("1", "2020-01-01T10:00:00Z").replace("\"OKOYFIHH\"", "\"\"");
+        let transaction: Transaction = serde_json::from_str(&json).unwrap();
+        let creditor = transaction.creditor.unwrap();
+        assert_eq!(None, creditor.bic());
+        assert_eq!("OP Ryhmä", creditor.bank().unwrap().name);
+        assert_eq!(
+            Some("OKOYFIHH"),
+            creditor.resolved_bic().as_ref().map(Bic::as_str)
+        );
+    }
+
+    #[test]
+    fn test_national_reference() {
+        let reference = Reference::parse("12 34561").unwrap();
+        assert_eq!(ReferenceKind::National, reference.kind());
+        assert_eq!("1234561", reference.electronic());
+        assert_eq!("12 34561", reference.print());
+        assert_eq!(reference, Reference::national("123456").unwrap());
+        assert_eq!("1232", Reference::national("123").unwrap().electronic());
+        assert_eq!("1232", Reference::parse("0001232").unwrap().electronic());
+
+        assert_eq!(
+            Err(ValidationError::InvalidChecksum),
+            Reference::parse("1234562")
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidLength(3)),
+            Reference::parse("123")
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidCharacter('A')),
+            Reference::national("12A")
+        );
+    }
+
+    #[test]
+    fn test_rf_reference() {
+        let reference = Reference::parse("rf18 5390 0754 7034").unwrap();
+        assert_eq!(ReferenceKind::Rf, reference.kind());
+        assert_eq!("RF18539007547034", reference.electronic());
+        assert_eq!("RF18 5390 0754 7034", reference.print());
+        assert_eq!(reference, Reference::rf("539007547034").unwrap());
+
+        assert_eq!(
+            Err(ValidationError::InvalidChecksum),
+            Reference::parse("RF19539007547034")
+        );
+        assert!(Reference::parse("RF18").is_err());
+        assert!(Reference::rf("12345678901234567890AB").is_err());
+    }
+
+    #[test]
+    fn test_reference_conversion() {
+        let national = Reference::parse("1232").unwrap();
+        let rf = national.to_rf();
+        assert_eq!("RF111232", rf.electronic());
+        assert_eq!(Some(national.clone()), rf.to_national());
+        assert_eq!(Some(national.clone()), national.to_national());
+        assert_eq!(rf, rf.to_rf());
+
+        let rf = Reference::rf("INVOICE1").unwrap();
+        assert_eq!(None, rf.to_national());
+    }
+
+    #[test]
+    fn test_transaction_reference() {
+        let json = transaction_json("1", "2020-01-01T10:00:00Z");
+        let transaction: Transaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            Some("1232"),
+            transaction
+                .reference_number()
+                .as_ref()
+                .map(Reference::electronic)
+        );
+
+        let json = json.replace("\"1232\"", "\"1233\"");
+        let transaction: Transaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(None, transaction.reference_number());
+    }
+
+    #[test]
+    fn test_bank_transaction_code() {
+        let code = BankTransactionCode::parse("pmnt-icdt-esct").unwrap();
+        assert_eq!("PMNT", code.domain());
+        assert_eq!("ICDT", code.family());
+        assert_eq!("ESCT", code.sub_family());
+        assert_eq!(Some("Payments"), code.domain_description());
+        assert_eq!(Some("Issued Credit Transfers"), code.family_description());
+        assert_eq!(Some("SEPA Credit Transfer"), code.sub_family_description());
+        assert_eq!(
+            "Payments / Issued Credit Transfers / SEPA Credit Transfer",
+            code.description()
+        );
+        assert_eq!("PMNT-ICDT-ESCT", code.to_string());
+        assert!(code.is_credit_transfer());
+        assert!(code.is_sepa());
+        assert!(!code.is_card_transaction());
+
+        let code: BankTransactionCode = "PMNT-CCRD-POSD".parse().unwrap();
+        assert!(code.is_card_transaction());
+        let code: BankTransactionCode = "PMNT-RDDT-ESDD".parse().unwrap();
+        assert!(code.is_direct_debit());
+        let code: BankTransactionCode = "PMNT-CCRD-XBCW".parse().unwrap();
+        assert_eq!(
+            Some("Cross-Border Cash Withdrawal"),
+            code.sub_family_description()
+        );
+
+        let code: BankTransactionCode = "XTND-ABCD-EFGH".parse().unwrap();
+        assert_eq!(None, code.family_description());
+        assert_eq!("Extended Domain / ABCD / EFGH", code.description());
+
+        assert!(BankTransactionCode::parse("PMNT-ICDT").is_err());
+        assert!(BankTransactionCode::parse("PMNT-ICDT-ES").is_err());
+        assert_eq!(
+            Err(ValidationError::InvalidCharacter('_')),
+            BankTransactionCode::parse("PMNT-ICDT-ES_T")
+        );
+    }
+
+    #[test]
+    fn test_transaction_bank_transaction_code() {
+        let json = transaction_json("1", "2020-01-01T10:00:00Z");
+        let transaction: Transaction = serde_json::from_str(&json).unwrap();
+        let code = transaction.bank_transaction_code().unwrap();
+        assert_eq!(Some("Standing Order"), code.sub_family_description());
+    }
+
+    #[test]
+    fn test_op_transaction_code() {
+        let code = op_transaction_code("721").unwrap();
+        assert_eq!("Maksukorttimaksu", code.finnish);
+        assert_eq!("Card payment", code.english);
+        assert_eq!(TransactionCategory::CardPurchase, code.category);
+        assert_eq!(
+            TransactionCategory::Interest,
+            op_transaction_code(" 750 ").unwrap().category
+        );
+        assert_eq!(None, op_transaction_code("999"));
+    }
+
+    #[test]
+    fn test_transaction_category() {
+        let json = transaction_json("1", "2020-01-01T10:00:00Z");
+        let transaction: Transaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            "Talletus",
+            transaction.op_transaction_code_info().unwrap().finnish
+        );
+        assert_eq!(TransactionCategory::Deposit, transaction.category());
+
+        let category = |iso: &str, op: &str| {
+            let json = transaction_json("1", "2020-01-01T10:00:00Z")
+                .replace("PMNT-ICDT-STDO", iso)
+                .replace("\"710\"", &format!("\"{}\"", op));
+            serde_json::from_str::<Transaction>(&json)
+                .unwrap()
+                .category()
+        };
+        assert_eq!(
+            TransactionCategory::Salary,
+            category("PMNT-RCDT-SALA", "710")
+        );
+        assert_eq!(
+            TransactionCategory::AtmWithdrawal,
+            category("PMNT-CCRD-CWDL", "720")
+        );
+        assert_eq!(
+            TransactionCategory::CardPurchase,
+            category("PMNT-CCRD-POSD", "720")
+        );
+        assert_eq!(
+            TransactionCategory::EInvoice,
+            category("PMNT-ICDT-ESCT", "702")
+        );
+        assert_eq!(TransactionCategory::Other, category("", "999"));
+    }
+}
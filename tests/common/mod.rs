@@ -0,0 +1,294 @@
+//! Local stand-ins for tests that must not depend on the OP sandbox.
+#![allow(dead_code)]
+
+use op_api_sdk::error::TransportError;
+use op_api_sdk::transport::{async_trait, HttpRequest, HttpResponse, Transport};
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::StatusCode;
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Canned response served by the MockServer.
+#[derive(Clone, Debug)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl MockResponse {
+    /// Creates new response with JSON body.
+    pub fn json(status: u16, body: &str) -> MockResponse {
+        MockResponse {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string(),
+        }
+    }
+
+    /// Adds header to the response.
+    pub fn with_header(mut self, name: &str, value: &str) -> MockResponse {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// Request received by the MockServer.
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    /// Returns value of the header with case insensitive name.
+    pub fn header(&self, name: &str) -> Option<String> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.clone())
+    }
+}
+
+/// Minimal keep-alive HTTP/1.1 server serving canned responses in order.
+///
+/// When all responses have been served, the server answers 404.
+pub struct MockServer {
+    port: u16,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    connections: Arc<AtomicUsize>,
+}
+
+impl MockServer {
+    /// Starts new server in background thread.
+    pub fn start(responses: Vec<MockResponse>) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let responses = Arc::new(Mutex::new(VecDeque::from(responses)));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let (reqs, conns) = (requests.clone(), connections.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(s) => s,
+                    Err(_) => break,
+                };
+                conns.fetch_add(1, Ordering::SeqCst);
+                let (responses, reqs) = (responses.clone(), reqs.clone());
+                thread::spawn(move || serve(stream, responses, reqs));
+            }
+        });
+
+        MockServer {
+            port,
+            requests,
+            connections,
+        }
+    }
+
+    /// Base URL of the server to be used with Options::set_base_url.
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    /// Returns all requests received so far.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Returns number of TCP connections accepted so far.
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+/// Serves requests from a single connection until client closes it.
+fn serve(
+    stream: TcpStream,
+    responses: Arc<Mutex<VecDeque<MockResponse>>>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    while let Some(request) = read_request(&mut reader) {
+        requests.lock().unwrap().push(request);
+        let response = responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| MockResponse::json(404, ""));
+        if write_response(&mut writer, &response).is_err() {
+            return;
+        }
+    }
+}
+
+fn read_request(reader: &mut BufReader<TcpStream>) -> Option<RecordedRequest> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).ok()? == 0 {
+        return None;
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_at(line.find(':')?);
+        headers.push((name.to_string(), value[1..].trim().to_string()));
+    }
+
+    let length = headers
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, v)| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn write_response(writer: &mut TcpStream, response: &MockResponse) -> std::io::Result<()> {
+    let mut head = format!("HTTP/1.1 {} Mock\r\n", response.status);
+    for (name, value) in response.headers.iter() {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!("Content-Length: {}\r\n\r\n", response.body.len()));
+    writer.write_all(head.as_bytes())?;
+    writer.write_all(response.body.as_bytes())?;
+    writer.flush()
+}
+
+/// In-memory transport serving canned responses in order and recording
+/// all requests.
+///
+/// When all responses have been served, the transport answers 404.
+#[derive(Default)]
+pub struct FakeTransport {
+    responses: Mutex<VecDeque<Result<HttpResponse, TransportError>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl FakeTransport {
+    /// Creates new transport serving given responses.
+    pub fn new(responses: Vec<MockResponse>) -> Arc<FakeTransport> {
+        let transport = FakeTransport::default();
+        for response in responses {
+            transport.push(response);
+        }
+        Arc::new(transport)
+    }
+
+    /// Adds response to be served.
+    pub fn push(&self, response: MockResponse) {
+        let mut headers = HeaderMap::new();
+        for (name, value) in response.headers.iter() {
+            headers.append(
+                reqwest::header::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        self.responses.lock().unwrap().push_back(Ok(HttpResponse {
+            status: StatusCode::from_u16(response.status).unwrap(),
+            headers,
+            body: response.body.into_bytes(),
+        }));
+    }
+
+    /// Adds transport error to be returned.
+    pub fn push_error(&self, error: TransportError) {
+        self.responses.lock().unwrap().push_back(Err(error));
+    }
+
+    /// Returns all requests sent so far.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Transport for FakeTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        self.requests.lock().unwrap().push(request);
+        self.responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| {
+                Ok(HttpResponse {
+                    status: StatusCode::NOT_FOUND,
+                    headers: HeaderMap::new(),
+                    body: Vec::new(),
+                })
+            })
+    }
+}
+
+/// Returns JSON of a single transaction with given id and booking time.
+pub fn transaction_json(id: &str, booking: &str) -> String {
+    format!(
+        r#"{{
+            "transactionId": "{id}",
+            "accountId": "acc-1",
+            "archiveId": "20200101123456789",
+            "reference": "1232",
+            "message": "Invoice {id}",
+            "amount": "-12.50",
+            "currency": "EUR",
+            "creditDebitIndicator": "debit",
+            "accountBalance": "100.00",
+            "creditor": {{
+                "accountIdentifierType": "IBAN",
+                "accountName": "Shop Oy",
+                "accountIdentifier": "FI4250001510000023",
+                "servicerIdentifier": "OKOYFIHH",
+                "servicerIdentifierType": "BIC"
+            }},
+            "debtor": null,
+            "bookingDateTime": "{booking}",
+            "valueDateTime": "{booking}",
+            "status": "Authorised",
+            "isoTransactionCode": "PMNT-ICDT-STDO",
+            "opTransactionCode": "710"
+        }}"#,
+        id = id,
+        booking = booking
+    )
+}
+
+/// Returns JSON of a transactions page with optional paging token for
+/// the next page.
+pub fn transactions_page(transactions: &[String], next_token: Option<&str>) -> String {
+    let next = match next_token {
+        Some(token) => format!(
+            r#"{{"href": "/accounts/v3/accounts/acc-1/transactions?forwardPagingToken={}"}}"#,
+            token
+        ),
+        None => "null".to_string(),
+    };
+    format!(
+        r#"{{"transactions": [{}], "_links": {{"next": {}}}}}"#,
+        transactions.join(","),
+        next
+    )
+}
@@ -0,0 +1,152 @@
+mod common;
+
+#[cfg(test)]
+mod errors_tests {
+    use super::common::{MockResponse, MockServer};
+    use op_api_sdk::client::Client;
+    use op_api_sdk::error::SdkError;
+    use op_api_sdk::options::{Options, RetryOptions};
+
+    const ERRORS: &str = r#"{"errors": [{"id": "42", "level": "error", "type": "not_found", "message": "No such account"}]}"#;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    fn client(server: &MockServer) -> Client {
+        let options = Options::new_dev("key".to_string());
+        options.set_base_url(server.url());
+        options.set_retry_options(RetryOptions::disabled());
+        Client::new(options)
+    }
+
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+    #[test]
+    fn test_error_is_send_sync() {
+        assert_send_sync::<SdkError>();
+    }
+
+    #[tokio::test]
+    async fn test_http_error() {
+        init();
+        let server = MockServer::start(vec![MockResponse::json(404, ERRORS)]);
+        let resp = client(&server).account("missing".to_string()).await;
+
+        match resp {
+            Err(SdkError::Http(response)) => {
+                assert_eq!(404, response.status.as_u16());
+                let errors = response.errors.unwrap();
+                assert_eq!("42", errors.errors[0].id);
+                assert_eq!("No such account", errors.errors[0].message);
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_non_json_error_body() {
+        init();
+        let html = "<html><body>Bad Gateway</body></html>";
+        let server = MockServer::start(vec![MockResponse {
+            status: 502,
+            headers: vec![("Content-Type".to_string(), "text/html".to_string())],
+            body: html.to_string(),
+        }]);
+        let resp = client(&server).accounts().await;
+
+        match resp {
+            Err(SdkError::Http(response)) => {
+                assert_eq!(502, response.status.as_u16());
+                assert_eq!(html, response.body);
+                assert_eq!("text/html", response.headers["content-type"]);
+                assert!(response.errors.is_none());
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_error_body() {
+        init();
+        let server = MockServer::start(vec![MockResponse::json(401, "")]);
+        let resp = client(&server).accounts().await;
+
+        match resp {
+            Err(SdkError::Authentication(response)) => {
+                assert_eq!(401, response.status.as_u16());
+                assert!(response.body.is_empty());
+                assert!(response.errors.is_none());
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_all_2xx_are_successful() {
+        init();
+        let server = MockServer::start(vec![
+            MockResponse::json(201, r#"{"accounts": []}"#),
+            MockResponse::json(203, r#"{"accounts": []}"#),
+        ]);
+        let client = client(&server);
+
+        assert!(client.accounts().await.is_ok());
+        assert!(client.accounts().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_authentication_and_consent_errors() {
+        init();
+        let server = MockServer::start(vec![
+            MockResponse::json(401, ERRORS),
+            MockResponse::json(403, ERRORS),
+        ]);
+        let client = client(&server);
+
+        let resp = client.accounts().await;
+        assert!(matches!(resp, Err(SdkError::Authentication { .. })));
+        let resp = client.accounts().await;
+        assert!(matches!(resp, Err(SdkError::Consent { .. })));
+        assert_eq!(403, resp.err().unwrap().status().unwrap().as_u16());
+    }
+
+    #[tokio::test]
+    async fn test_decode_error_keeps_body() {
+        init();
+        let server = MockServer::start(vec![MockResponse::json(200, r#"{"unexpected": 1}"#)]);
+        let resp = client(&server).accounts().await;
+
+        match resp {
+            Err(SdkError::Decode { body, .. }) => assert_eq!(r#"{"unexpected": 1}"#, body),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decode_error_display_is_truncated() {
+        init();
+        let long = format!(r#"{{"unexpected": "{}"}}"#, "x".repeat(500));
+        let server = MockServer::start(vec![MockResponse::json(200, &long)]);
+        let error = client(&server).accounts().await.err().unwrap();
+
+        let message = error.to_string();
+        assert!(message.starts_with("Failed to decode response: "));
+        assert!(message.len() < 300);
+        match error {
+            SdkError::Decode { body, .. } => assert_eq!(long, body),
+            other => panic!("Unexpected error: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transport_error() {
+        init();
+        let options = Options::new_dev("key".to_string());
+        options.set_base_url("http://127.0.0.1:1".to_string());
+        options.set_retry_options(RetryOptions::disabled());
+        let resp = Client::new(options).accounts().await;
+
+        assert!(matches!(resp, Err(SdkError::Transport(_))));
+    }
+}
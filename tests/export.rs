@@ -0,0 +1,638 @@
+mod common;
+
+#[cfg(test)]
+mod export_tests {
+    use super::common::transaction_json;
+    use chrono::NaiveDate;
+    use op_api_sdk::banking::{Iban, Reference};
+    use op_api_sdk::export::camt053;
+    use op_api_sdk::export::csv::{self, CsvColumn, CsvOptions};
+    use op_api_sdk::export::ledger::{self, AccountRule, LedgerFormat, LedgerOptions};
+    use op_api_sdk::export::ofx::{self, OfxFormat};
+    use op_api_sdk::export::qif;
+    use op_api_sdk::export::tito;
+    use op_api_sdk::export::Locale;
+    use op_api_sdk::model::accounts::{Account, Transaction};
+    use op_api_sdk::model::time;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    fn transactions() -> Vec<Transaction> {
+        let debit = transaction_json("1", "2020-01-31T22:30:00Z");
+        let credit = transaction_json("2", "2020-02-03T10:00:00Z")
+            .replace("\"debit\"", "\"credit\"")
+            .replace("\"-12.50\"", "\"1234.5\"")
+            .replace("\"100.00\"", "\"1334.50\"")
+            .replace(
+                "\"debtor\": null",
+                r#""debtor": {
+                "accountIdentifierType": "IBAN",
+                "accountName": "Matti Meikäläinen; Oy",
+                "accountIdentifier": "FI2112345600000785",
+                "servicerIdentifier": "",
+                "servicerIdentifierType": "BIC"
+            }"#,
+            )
+            .replace("Invoice 2", "Invoice \\\"2\\\"");
+        vec![
+            serde_json::from_str(&debit).unwrap(),
+            serde_json::from_str(&credit).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_international_csv() {
+        let transactions = transactions();
+        let output = csv::to_string(&transactions, &CsvOptions::international()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            "Booking date,Value date,Amount,Currency,Counterparty,Counterparty account,\
+             Reference,Message,Archive ID,Transaction ID",
+            lines[0]
+        );
+        assert_eq!(
+            "2020-02-01,2020-02-01,-12.50,EUR,Shop Oy,FI4250001510000023,1232,Invoice 1,\
+             20200101123456789,1",
+            lines[1]
+        );
+        assert_eq!(
+            "2020-02-03,2020-02-03,1234.50,EUR,Matti Meikäläinen; Oy,FI2112345600000785,1232,\
+             \"Invoice \"\"2\"\"\",20200101123456789,2",
+            lines[2]
+        );
+    }
+
+    #[test]
+    fn test_finnish_csv() {
+        let transactions = transactions();
+        let output = csv::to_string(&transactions, &CsvOptions::finnish()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].starts_with("Kirjauspäivä;Arvopäivä;Määrä;"));
+        assert!(lines[1].starts_with("1.2.2020;1.2.2020;-12,50;EUR;Shop Oy;"));
+        assert!(lines[2].starts_with("3.2.2020;3.2.2020;1234,50;EUR;\"Matti Meikäläinen; Oy\";"));
+    }
+
+    #[test]
+    fn test_csv_amount_sign() {
+        let debit =
+            transaction_json("1", "2020-01-31T22:30:00Z").replace("\"-12.50\"", "\"12.50\"");
+        let credit = transaction_json("2", "2020-02-03T10:00:00Z")
+            .replace("\"debit\"", "\"credit\"")
+            .replace("\"-12.50\"", "\"-1234.5\"");
+        let transactions: Vec<Transaction> = vec![
+            serde_json::from_str(&debit).unwrap(),
+            serde_json::from_str(&credit).unwrap(),
+        ];
+        let options = CsvOptions::international()
+            .with_columns(vec![CsvColumn::Amount, CsvColumn::CreditDebitIndicator])
+            .with_headers(false);
+        let output = csv::to_string(&transactions, &options).unwrap();
+        assert_eq!("-12.50,debit\n1234.50,credit\n", output);
+    }
+
+    #[test]
+    fn test_columns_and_delimiter() {
+        let transactions = transactions();
+        let options = CsvOptions::default()
+            .with_columns(vec![
+                CsvColumn::TransactionId,
+                CsvColumn::CreditorName,
+                CsvColumn::DebtorName,
+                CsvColumn::DebtorServicer,
+                CsvColumn::CreditDebitIndicator,
+            ])
+            .with_locale(Locale::Finnish)
+            .with_delimiter(b'\t')
+            .with_headers(false);
+        let output = csv::to_string(&transactions, &options).unwrap();
+        assert_eq!(
+            "1\tShop Oy\t\t\tdebit\n2\tShop Oy\tMatti Meikäläinen; Oy\tNDEAFIHH\tcredit\n",
+            output
+        );
+    }
+
+    const ACCOUNT: &str = r#"{
+        "accountId": "acc-1",
+        "name": "Käyttötili & säästö",
+        "nickname": null,
+        "balance": 1334.5,
+        "currency": "EUR",
+        "identifierScheme": "IBAN",
+        "identifier": "FI4250001510000023",
+        "servicerScheme": "BIC",
+        "servicerIdentifier": "OKOYFIHH"
+    }"#;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_camt053() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        let transactions = transactions();
+        let xml = camt053::to_string(&account, date(2020, 2, 1), date(2020, 2, 29), &transactions)
+            .unwrap();
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.contains("<Document xmlns=\"urn:iso:std:iso:20022:tech:xsd:camt.053.001.02\">"));
+        assert!(xml.contains("<MsgId>acc-1-20200201-20200229</MsgId>"));
+        assert!(xml.contains("<FrDtTm>2020-02-01T00:00:00</FrDtTm>"));
+        assert!(xml.contains("<ToDtTm>2020-02-29T23:59:59</ToDtTm>"));
+        assert!(xml.contains("<IBAN>FI4250001510000023</IBAN>"));
+        assert!(xml.contains("<Nm>Käyttötili &amp; säästö</Nm>"));
+
+        let compact: String = xml.split_whitespace().collect();
+        assert!(compact.contains(
+            "<Cd>OPBD</Cd></CdOrPrtry></Tp><AmtCcy=\"EUR\">112.50</Amt><CdtDbtInd>CRDT</CdtDbtInd>"
+        ));
+        assert!(compact.contains(
+            "<Cd>CLBD</Cd></CdOrPrtry></Tp><AmtCcy=\"EUR\">1334.50</Amt><CdtDbtInd>CRDT</CdtDbtInd>"
+        ));
+        assert!(compact
+            .contains("<TtlCdtNtries><NbOfNtries>1</NbOfNtries><Sum>1234.50</Sum></TtlCdtNtries>"));
+        assert!(compact
+            .contains("<TtlDbtNtries><NbOfNtries>1</NbOfNtries><Sum>12.50</Sum></TtlDbtNtries>"));
+        assert!(compact.contains(
+            "<NtryRef>1</NtryRef><AmtCcy=\"EUR\">12.50</Amt><CdtDbtInd>DBIT</CdtDbtInd>\
+             <Sts>BOOK</Sts><BookgDt><Dt>2020-02-01</Dt></BookgDt>"
+        ));
+        assert!(compact.contains(
+            "<BkTxCd><Domn><Cd>PMNT</Cd><Fmly><Cd>ICDT</Cd><SubFmlyCd>STDO</SubFmlyCd></Fmly>\
+             </Domn><Prtry><Cd>710</Cd></Prtry></BkTxCd>"
+        ));
+        assert!(compact
+            .contains("<Cdtr><Nm>ShopOy</Nm></Cdtr><CdtrAcct><Id><IBAN>FI4250001510000023</IBAN>"));
+        assert!(compact.contains("<DbtrAgt><FinInstnId><BIC>NDEAFIHH</BIC>"));
+        assert!(compact.contains("<Ustrd>Invoice&quot;2&quot;</Ustrd>"));
+        assert!(compact
+            .contains("<CdtrRefInf><Tp><CdOrPrtry><Cd>SCOR</Cd></CdOrPrtry></Tp><Ref>1232</Ref>"));
+        assert_eq!(2, xml.matches("<Ntry>").count());
+    }
+
+    #[test]
+    fn test_camt053_period() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        let transactions = transactions();
+
+        let xml = camt053::to_string(&account, date(2020, 2, 2), date(2020, 2, 29), &transactions)
+            .unwrap();
+        assert_eq!(1, xml.matches("<Ntry>").count());
+        assert!(xml.contains("<NtryRef>2</NtryRef>"));
+
+        assert!(
+            camt053::to_string(&account, date(2020, 3, 1), date(2020, 3, 31), &transactions)
+                .is_err()
+        );
+        let today = time::local_today();
+        let xml = camt053::to_string(&account, today, today, &transactions).unwrap();
+        assert_eq!(0, xml.matches("<Ntry>").count());
+        assert_eq!(2, xml.matches(">1334.50</Amt>").count());
+
+        assert!(
+            camt053::to_string(&account, date(2020, 3, 1), date(2020, 2, 1), &transactions)
+                .is_err()
+        );
+    }
+
+    fn uncoded_transaction() -> Transaction {
+        let json = transaction_json(&"9".repeat(40), "2020-02-05T10:00:00Z")
+            .replace("20200101123456789", &"A".repeat(40))
+            .replace("\"PMNT-ICDT-STDO\"", "null")
+            .replace("\"710\"", "null")
+            .replace("\"Shop Oy\"", "\"\"");
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_camt053_mandatory_elements() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        let transactions = vec![uncoded_transaction()];
+        let xml = camt053::to_string(&account, date(2020, 2, 1), date(2020, 2, 29), &transactions)
+            .unwrap();
+
+        let compact: String = xml.split_whitespace().collect();
+        assert!(compact.contains("<BkTxCd><Prtry><Cd>NTAV</Cd></Prtry></BkTxCd>"));
+        assert!(compact.contains(&format!("<NtryRef>{}</NtryRef>", "9".repeat(35))));
+        assert!(compact.contains(&format!("<TxId>{}</TxId>", "9".repeat(35))));
+        assert_eq!(
+            2,
+            compact
+                .matches(&format!("<AcctSvcrRef>{}</AcctSvcrRef>", "A".repeat(35)))
+                .count()
+        );
+        assert!(compact.contains("<Cdtr></Cdtr>"));
+    }
+
+    #[test]
+    fn test_camt053_schema() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        let mut transactions = transactions();
+        transactions.push(uncoded_transaction());
+        let xml = camt053::to_string(&account, date(2020, 2, 1), date(2020, 2, 29), &transactions)
+            .unwrap();
+
+        let schema = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/camt.053.001.02.xsd"
+        );
+        let xmllint = Command::new("xmllint")
+            .args(["--noout", "--schema", schema, "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn();
+        let mut xmllint = match xmllint {
+            Ok(xmllint) => xmllint,
+            Err(e) => {
+                eprintln!("Skipping schema validation, xmllint not available: {}", e);
+                return;
+            }
+        };
+        xmllint
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(xml.as_bytes())
+            .unwrap();
+        let output = xmllint.wait_with_output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn test_camt053_simultaneous_entries() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        let first = transaction_json("1", "2020-02-03T10:00:00Z");
+        let second =
+            transaction_json("2", "2020-02-03T10:00:00Z").replace("\"100.00\"", "\"87.50\"");
+        let transactions: Vec<Transaction> = vec![
+            serde_json::from_str(&second).unwrap(),
+            serde_json::from_str(&first).unwrap(),
+        ];
+        let xml = camt053::to_string(&account, date(2020, 2, 1), date(2020, 2, 29), &transactions)
+            .unwrap();
+
+        let compact: String = xml.split_whitespace().collect();
+        assert!(compact.contains("<Cd>OPBD</Cd></CdOrPrtry></Tp><AmtCcy=\"EUR\">112.50</Amt>"));
+        assert!(compact.contains("<Cd>CLBD</Cd></CdOrPrtry></Tp><AmtCcy=\"EUR\">87.50</Amt>"));
+        assert!(xml.find("<NtryRef>1</NtryRef>") < xml.find("<NtryRef>2</NtryRef>"));
+    }
+
+    #[test]
+    fn test_ofx_sgml() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        let transactions = transactions();
+        let output = ofx::to_string(&account, &transactions, OfxFormat::Sgml).unwrap();
+
+        assert!(output.starts_with("OFXHEADER:100\r\nDATA:OFXSGML\r\nVERSION:102\r\n"));
+        assert!(!output.contains("</TRNAMT>"));
+        let compact: String = output.split_whitespace().collect();
+        assert!(compact.contains(
+            "<BANKACCTFROM><BANKID>OKOYFIHH<ACCTID>FI4250001510000023<ACCTTYPE>CHECKING\
+             </BANKACCTFROM>"
+        ));
+        assert!(
+            compact.contains("<DTSTART>20200131223000.000[0:GMT]<DTEND>20200203100000.000[0:GMT]")
+        );
+        assert!(compact.contains(
+            "<STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20200131223000.000[0:GMT]\
+             <DTAVAIL>20200131223000.000[0:GMT]<TRNAMT>-12.50<FITID>1<REFNUM>1232\
+             <NAME>ShopOy<MEMO>Invoice1</STMTTRN>"
+        ));
+        assert!(compact.contains("<TRNTYPE>CREDIT"));
+        assert!(compact.contains("<TRNAMT>1234.50<FITID>2"));
+        assert!(compact.contains("<NAME>MattiMeikäläinen;Oy<MEMO>Invoice\"2\""));
+        assert!(compact.contains("<LEDGERBAL><BALAMT>1334.50<DTASOF>"));
+        assert!(compact.ends_with("</STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>"));
+    }
+
+    #[test]
+    fn test_ofx_bank_id() {
+        let transactions = transactions();
+        for (servicer, expected) in [
+            ("OKOYFIHHXXX", "OKOYFIHH"),
+            ("OKOYFIHH", "OKOYFIHH"),
+            ("not a valid bic", "not a val"),
+        ] {
+            let json = ACCOUNT
+                .replace("\"OKOYFIHH\"", &format!("\"{}\"", servicer))
+                .replace("\"FI4250001510000023\"", "\"DE89370400440532013000\"");
+            let account: Account = serde_json::from_str(&json).unwrap();
+            let output = ofx::to_string(&account, &transactions, OfxFormat::Xml).unwrap();
+            let start = output.find("<BANKID>").unwrap() + "<BANKID>".len();
+            let end = output.find("</BANKID>").unwrap();
+            let bank_id = &output[start..end];
+            assert!(bank_id.chars().count() <= 9);
+            assert_eq!(expected, bank_id);
+        }
+    }
+
+    #[test]
+    fn test_ofx_sgml_escaping() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        let mut transactions = transactions();
+        transactions[0].creditor.as_mut().unwrap().account_name = "O'Brien & Sons".to_string();
+        let output = ofx::to_string(&account, &transactions, OfxFormat::Sgml).unwrap();
+        assert!(output.contains("<NAME>O'Brien &amp; Sons\n"));
+        assert!(output.contains("<MEMO>Invoice \"2\""));
+        assert!(!output.contains("&apos;"));
+        assert!(!output.contains("&quot;"));
+
+        let output = ofx::to_string(&account, &transactions, OfxFormat::Xml).unwrap();
+        assert!(output.contains("<NAME>O&apos;Brien &amp; Sons</NAME>"));
+    }
+
+    #[test]
+    fn test_ofx_xml() {
+        let mut account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        account.balance = None;
+        let transactions = transactions();
+        let output = ofx::to_string(&account, &transactions, OfxFormat::Xml).unwrap();
+
+        assert!(output.starts_with(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <?OFX OFXHEADER=\"200\" VERSION=\"211\" SECURITY=\"NONE\" \
+             OLDFILEUID=\"NONE\" NEWFILEUID=\"NONE\"?>"
+        ));
+        assert!(output.contains("<TRNAMT>-12.50</TRNAMT>"));
+        assert!(output.contains("<FITID>1</FITID>"));
+        let compact: String = output.split_whitespace().collect();
+        assert!(compact.contains(
+            "<LEDGERBAL><BALAMT>1334.50</BALAMT><DTASOF>20200203100000.000[0:GMT]</DTASOF>"
+        ));
+
+        assert!(ofx::to_string(&account, &[], OfxFormat::Xml).is_err());
+    }
+
+    #[test]
+    fn test_qif() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        let mut transactions = transactions();
+        transactions.reverse();
+        transactions[0].message = Some("Line\nbreak".to_string());
+        transactions[1].message = None;
+
+        let output = qif::to_string(&account, &transactions, Locale::Finnish).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            vec![
+                "!Account",
+                "NKäyttötili & säästö",
+                "TBank",
+                "DFI42 5000 1510 0000 23",
+            ],
+            lines[..4].to_vec()
+        );
+        assert!(lines[4].starts_with('/'));
+        assert_eq!(
+            vec![
+                "$1334,50",
+                "^",
+                "!Type:Bank",
+                "D1.2.2020",
+                "T-12,50",
+                "N1",
+                "PShop Oy",
+                "M1232",
+                "^",
+                "D3.2.2020",
+                "T1234,50",
+                "N2",
+                "PMatti Meikäläinen; Oy",
+                "MLine break",
+                "^",
+            ],
+            lines[5..].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_ledger() {
+        let transactions = transactions();
+        let output = ledger::to_string(&transactions, &LedgerOptions::default()).unwrap();
+        assert_eq!(
+            "2020-02-01 * (20200101123456789) Shop Oy\n\
+             \x20   ; Invoice 1\n\
+             \x20   ; transaction_id: 1\n\
+             \x20   Assets:Bank:OP  -12.50 EUR = 100.00 EUR\n\
+             \x20   Expenses:Uncategorized  12.50 EUR\n\
+             \n\
+             2020-02-03 * (20200101123456789) Matti Meikäläinen; Oy\n\
+             \x20   ; Invoice \"2\"\n\
+             \x20   ; transaction_id: 2\n\
+             \x20   Assets:Bank:OP  1234.50 EUR = 1334.50 EUR\n\
+             \x20   Income:Uncategorized  -1234.50 EUR\n\
+             \n",
+            output
+        );
+    }
+
+    #[test]
+    fn test_hledger_rules() {
+        let transactions = transactions();
+        let options = LedgerOptions::new(LedgerFormat::Hledger)
+            .with_account("Assets:Checking")
+            .with_rule(AccountRule::message("^Invoice \\d$", "Expenses:Bills").unwrap())
+            .with_rule(AccountRule::reference(
+                Reference::parse("1232").unwrap(),
+                "Income:Sales",
+            ))
+            .with_balance_assertions(false);
+        let output = ledger::to_string(&transactions, &options).unwrap();
+        assert!(output.contains("2020-02-01 * (20200101123456789) Shop Oy | Invoice 1\n"));
+        assert!(output.contains("    Assets:Checking  -12.50 EUR\n    Expenses:Bills  12.50 EUR\n"));
+        assert!(
+            output.contains("    Assets:Checking  1234.50 EUR\n    Income:Sales  -1234.50 EUR\n")
+        );
+        assert!(!output.contains(" = "));
+
+        assert!(AccountRule::message("(unclosed", "Expenses:Bills").is_err());
+    }
+
+    #[test]
+    fn test_ledger_escaping() {
+        let json = transaction_json("1", "2020-02-01T10:00:00Z")
+            .replace("Shop Oy", "Shop | Café Oy")
+            .replace("Invoice 1", "Ref: 42 | paid");
+        let transactions: Vec<Transaction> = vec![serde_json::from_str(&json).unwrap()];
+
+        let output = ledger::to_string(&transactions, &LedgerOptions::default()).unwrap();
+        assert!(output.contains(" Shop / Café Oy\n    ; Ref - 42 / paid\n"));
+
+        let options = LedgerOptions::new(LedgerFormat::Hledger);
+        let output = ledger::to_string(&transactions, &options).unwrap();
+        assert!(output.contains(" Shop / Café Oy | Ref - 42 / paid\n"));
+    }
+
+    #[test]
+    fn test_cancelled_transactions_are_skipped() {
+        let mut transactions = transactions();
+        for (id, status) in [("3", "Rejected"), ("4", "Revoked")] {
+            let json = transaction_json(id, "2020-02-05T10:00:00Z")
+                .replace("\"Authorised\"", &format!("\"{}\"", status));
+            transactions.push(serde_json::from_str(&json).unwrap());
+        }
+
+        for format in [LedgerFormat::Ledger, LedgerFormat::Beancount] {
+            let output = ledger::to_string(&transactions, &LedgerOptions::new(format)).unwrap();
+            assert!(output.contains("Matti Meikäläinen; Oy"));
+            assert!(!output.contains("Invoice 3"));
+            assert!(!output.contains("Invoice 4"));
+            assert!(!output.contains("2020-02-05"));
+        }
+    }
+
+    #[test]
+    fn test_beancount() {
+        let mut transactions = transactions();
+        let second =
+            transaction_json("3", "2020-02-01T12:00:00Z").replace("\"100.00\"", "\"87.50\"");
+        transactions.push(serde_json::from_str(&second).unwrap());
+
+        let options = LedgerOptions::new(LedgerFormat::Beancount).with_rule(AccountRule::iban(
+            Iban::parse("FI21 1234 5600 0007 85").unwrap(),
+            "Income:Salary",
+        ));
+        let output = ledger::to_string(&transactions, &options).unwrap();
+        assert_eq!(
+            "2020-02-01 * \"Shop Oy\" \"Invoice 1\"\n\
+             \x20 transaction_id: \"1\"\n\
+             \x20 archive_id: \"20200101123456789\"\n\
+             \x20 Assets:Bank:OP  -12.50 EUR\n\
+             \x20 Expenses:Uncategorized  12.50 EUR\n\
+             \n",
+            &output[..output.find("\n\n").unwrap() + 2]
+        );
+        assert_eq!(
+            1,
+            output
+                .matches("2020-02-02 balance Assets:Bank:OP  87.50 EUR\n")
+                .count()
+        );
+        assert_eq!(2, output.matches(" balance ").count());
+        assert!(output.contains("2020-02-03 * \"Matti Meikäläinen; Oy\" \"Invoice \\\"2\\\"\"\n"));
+        assert!(output.contains("  Income:Salary  -1234.50 EUR\n"));
+        assert!(output.ends_with("2020-02-04 balance Assets:Bank:OP  1334.50 EUR\n\n"));
+    }
+
+    /// Returns field of the record at 1-based position.
+    fn field(record: &str, position: usize, length: usize) -> String {
+        record.chars().skip(position - 1).take(length).collect()
+    }
+
+    #[test]
+    fn test_tito() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        let mut transactions = transactions();
+        let national = Reference::national("4567").unwrap();
+        transactions[1].reference = Some(national.to_rf().print());
+        transactions[1].message = Some("x".repeat(40));
+        let output =
+            tito::to_string(&account, date(2020, 2, 1), date(2020, 2, 29), &transactions).unwrap();
+        assert!(output.ends_with("\r\n"));
+        let records: Vec<&str> = output.split("\r\n").filter(|r| !r.is_empty()).collect();
+        let kinds: Vec<String> = records.iter().map(|r| field(r, 1, 6)).collect();
+        assert_eq!(
+            vec!["T00322", "T10188", "T11043", "T40050", "T10188", "T11078", "T40050", "T50067"],
+            kinds
+        );
+        for record in records.iter() {
+            assert_eq!(
+                field(record, 4, 3).parse::<usize>().unwrap(),
+                record.chars().count()
+            );
+        }
+
+        let basic = records[0];
+        assert!(basic.starts_with("T00322100"));
+        assert_eq!("100", field(basic, 7, 3));
+        assert_eq!("50001510000023", field(basic, 10, 14));
+        assert_eq!("200201200229", field(basic, 27, 12));
+        assert_eq!("200201", field(basic, 66, 6));
+        assert_eq!("+000000000000011250", field(basic, 72, 19));
+        assert_eq!("000008", field(basic, 91, 6));
+        assert_eq!("EUR", field(basic, 97, 3));
+        assert_eq!("Käyttötili & säästö", field(basic, 100, 30).trim_end());
+        assert_eq!(
+            "FI4250001510000023OKOYFIHH",
+            field(basic, 293, 29).trim_end()
+        );
+
+        let debit = records[1];
+        assert_eq!("000001", field(debit, 7, 6));
+        assert_eq!("20200101123456789 ", field(debit, 13, 18));
+        assert_eq!("200201200201200201", field(debit, 31, 18));
+        assert_eq!("2710Talletus", field(debit, 49, 39).trim_end());
+        assert_eq!("-000000000000001250", field(debit, 88, 19));
+        assert_eq!("Shop Oy", field(debit, 109, 35).trim_end());
+        assert_eq!("50001510000023", field(debit, 145, 14));
+        assert_eq!("00000000000000001232", field(debit, 160, 20));
+        assert_eq!(format!("T1104300{:35}", "Invoice 1"), records[2]);
+        assert_eq!(
+            "T40050200201+000000000000010000+000000000000010000",
+            records[3]
+        );
+
+        let credit = records[4];
+        assert_eq!("1", field(credit, 49, 1));
+        assert_eq!("+000000000000123450", field(credit, 88, 19));
+        assert_eq!("Matti Meikäläinen; Oy", field(credit, 109, 35).trim_end());
+        assert_eq!("12345600000785", field(credit, 145, 14));
+        assert_eq!(
+            format!("{:0>20}", national.electronic()),
+            field(credit, 160, 20)
+        );
+        assert_eq!(
+            format!("T1107800{:35}{:35}", "x".repeat(35), "xxxxx"),
+            records[5]
+        );
+        assert_eq!(
+            "T50067220022900000001+00000000000012345000000001-000000000000001250",
+            records[7]
+        );
+
+        let mut bytes = Vec::new();
+        tito::write_statement(
+            &mut bytes,
+            &account,
+            date(2020, 2, 1),
+            date(2020, 2, 29),
+            &transactions,
+        )
+        .unwrap();
+        let lines: Vec<&[u8]> = bytes.split(|b| *b == b'\n').collect();
+        assert_eq!(189, lines[4].len());
+        assert!(lines[4].contains(&0xE4));
+    }
+
+    #[test]
+    fn test_tito_rf_reference() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        let mut transactions = transactions();
+        let reference = Reference::rf("INVOICE2020").unwrap();
+        transactions[0].reference = Some(reference.electronic().to_string());
+        let output =
+            tito::to_string(&account, date(2020, 2, 1), date(2020, 2, 1), &transactions).unwrap();
+        let records: Vec<&str> = output.split("\r\n").collect();
+        assert_eq!("00000000000000000000", field(records[1], 160, 20));
+        assert_eq!(
+            format!("T1107800{:35}{:35}", reference.electronic(), "Invoice 1"),
+            records[2]
+        );
+
+        let today = time::local_today();
+        let output = tito::to_string(&account, today, today, &transactions).unwrap();
+        let records: Vec<&str> = output.split("\r\n").collect();
+        let today = today.format("%y%m%d");
+        assert_eq!("+000000000000133450", field(records[0], 72, 19));
+        assert_eq!(
+            format!("T40050{}+000000000000133450+000000000000133450", today),
+            records[1]
+        );
+        assert!(records[2].starts_with(&format!("T500672{}00000000+", today)));
+    }
+}
@@ -13,7 +13,6 @@ mod funds_tests {
     async fn test_funds() {
         init();
         let options = Options::new_dev(env::var("X_API_KEY").unwrap());
-        options.set_version("v1".to_string());
         let client = Client::new(options);
 
         // First test getting funds
@@ -0,0 +1,53 @@
+mod common;
+
+#[cfg(test)]
+mod http_tests {
+    use super::common::{MockResponse, MockServer};
+    use op_api_sdk::client::Client;
+    use op_api_sdk::options::{HttpOptions, Options};
+    use std::time::Duration;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[tokio::test]
+    async fn test_connection_is_reused() {
+        init();
+        let server = MockServer::start(vec![
+            MockResponse::json(200, r#"{"accounts": []}"#),
+            MockResponse::json(200, r#"{"accounts": []}"#),
+            MockResponse::json(200, r#"{"accounts": []}"#),
+        ]);
+        let options = Options::new_dev("key".to_string());
+        options.set_base_url(server.url());
+        let client = Client::new(options);
+
+        for _ in 0..3 {
+            let resp = client.accounts().await;
+            assert!(resp.is_ok(), "{:?}", resp.err());
+        }
+
+        assert_eq!(3, server.requests().len());
+        assert_eq!(1, server.connections());
+    }
+
+    #[tokio::test]
+    async fn test_http_options() {
+        init();
+        let server = MockServer::start(vec![MockResponse::json(200, r#"{"accounts": []}"#)]);
+        let options = Options::new_dev("key".to_string());
+        options.set_base_url(server.url());
+
+        let http = HttpOptions::default()
+            .with_pool_max_idle_per_host(4)
+            .with_tcp_keepalive(Some(Duration::from_secs(30)))
+            .with_timeout(Duration::from_secs(10));
+        assert!(options.set_http_options(http).is_ok());
+        assert_eq!(4, options.http_options().pool_max_idle_per_host);
+
+        let client = Client::new(options);
+        let resp = client.accounts().await;
+        assert!(resp.is_ok(), "{:?}", resp.err());
+    }
+}
@@ -0,0 +1,77 @@
+mod common;
+
+#[cfg(test)]
+mod logging_tests {
+    use super::common::{MockResponse, MockServer};
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+    use op_api_sdk::client::Client;
+    use op_api_sdk::options::Options;
+    use std::sync::Mutex;
+
+    /// Logger collecting all messages from the library.
+    struct CapturingLogger {
+        messages: Mutex<Vec<String>>,
+    }
+
+    impl Log for CapturingLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.level() <= Level::Debug
+        }
+
+        fn log(&self, record: &Record) {
+            self.messages
+                .lock()
+                .unwrap()
+                .push(format!("{}", record.args()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger {
+        messages: Mutex::new(Vec::new()),
+    };
+
+    fn init() {
+        let _ = log::set_logger(&LOGGER).map(|()| log::set_max_level(LevelFilter::Debug));
+    }
+
+    fn logged(needle: &str) -> bool {
+        LOGGER
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|m| m.contains(needle))
+    }
+
+    async fn send(api_key: &str, token: &str, log_secrets: bool) {
+        let server = MockServer::start(vec![MockResponse::json(200, r#"{"accounts": []}"#)]);
+        let options = Options::new(api_key.to_string(), token.to_string());
+        options.set_base_url(server.url());
+        options.set_log_secrets(log_secrets);
+        let resp = Client::new(options).accounts().await;
+        assert!(resp.is_ok(), "{:?}", resp.err());
+    }
+
+    #[tokio::test]
+    async fn test_secrets_are_redacted() {
+        init();
+        send("redacted-api-key", "redacted-token", false).await;
+
+        assert!(logged("Sending request"));
+        assert!(logged("x-api-key: <redacted>"));
+        assert!(logged("authorization: <redacted>"));
+        assert!(!logged("redacted-api-key"));
+        assert!(!logged("redacted-token"));
+    }
+
+    #[tokio::test]
+    async fn test_secrets_are_logged_when_enabled() {
+        init();
+        send("visible-api-key", "visible-token", true).await;
+
+        assert!(logged("x-api-key: visible-api-key"));
+        assert!(logged("authorization: Bearer visible-token"));
+    }
+}
@@ -0,0 +1,198 @@
+mod common;
+
+#[cfg(test)]
+mod model_tests {
+    use super::common::transaction_json;
+    use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+    use op_api_sdk::model::accounts::*;
+    use op_api_sdk::model::money::{parse_amount, Money, MoneyError};
+    use op_api_sdk::model::time;
+
+    const ACCOUNT: &str = r#"{
+        "accountId": "acc-1",
+        "name": "Käyttötili",
+        "nickname": null,
+        "balance": 100.5,
+        "currency": "EUR",
+        "identifierScheme": "IBAN",
+        "identifier": "FI4250001510000023",
+        "servicerScheme": "BIC",
+        "servicerIdentifier": "OKOYFIHH"
+    }"#;
+
+    #[test]
+    fn test_known_values() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        assert_eq!(IdentifierScheme::Iban, account.identifier_scheme);
+        assert_eq!(IdentifierScheme::Bic, account.servicer_scheme);
+
+        let json = transaction_json("1", "2020-01-01T10:00:00Z");
+        let transaction: Transaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            CreditDebitIndicator::Debit,
+            transaction.credit_debit_indicator
+        );
+        assert_eq!(Some(TransactionStatus::Authorised), transaction.status);
+        let creditor = transaction.creditor.unwrap();
+        assert_eq!(IdentifierScheme::Iban, creditor.account_identifier_type);
+        assert_eq!(IdentifierScheme::Bic, creditor.servicer_identifier_type);
+    }
+
+    #[test]
+    fn test_unknown_values() {
+        let json = transaction_json("1", "2020-01-01T10:00:00Z")
+            .replace("\"debit\"", "\"reversal\"")
+            .replace("\"Authorised\"", "\"Pending\"");
+        let transaction: Transaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            CreditDebitIndicator::Unknown("reversal".to_string()),
+            transaction.credit_debit_indicator
+        );
+        assert_eq!(
+            Some(TransactionStatus::Unknown("Pending".to_string())),
+            transaction.status
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(CreditDebitIndicator::Credit, "CREDIT".into());
+        assert_eq!("credit", CreditDebitIndicator::Credit.to_string());
+        assert_eq!(
+            "AwaitingAuthorisation",
+            String::from(TransactionStatus::AwaitingAuthorisation)
+        );
+        assert_eq!("BBAN", IdentifierScheme::from("BBAN").as_str());
+        assert_eq!(
+            "\"IBAN\"",
+            serde_json::to_string(&IdentifierScheme::Iban).unwrap()
+        );
+        assert_eq!(
+            "\"BBAN\"",
+            serde_json::to_string(&IdentifierScheme::Unknown("BBAN".to_string())).unwrap()
+        );
+    }
+
+    fn eur(amount: &str) -> Money {
+        Money::new(parse_amount(amount).unwrap(), "EUR".to_string())
+    }
+
+    #[test]
+    fn test_amounts() {
+        let account: Account = serde_json::from_str(ACCOUNT).unwrap();
+        assert_eq!(Some(eur("100.5")), account.balance_money());
+
+        let json = transaction_json("1", "2020-01-01T10:00:00Z");
+        let transaction: Transaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(eur("-12.50"), transaction.amount_money());
+        assert_eq!(eur("100"), transaction.balance_money());
+        assert!(transaction.amount_money().is_negative());
+
+        let json = json.replace("\"-12.50\"", "\"abc\"");
+        assert!(serde_json::from_str::<Transaction>(&json).is_err());
+    }
+
+    #[test]
+    fn test_money_arithmetic() {
+        assert_eq!(Ok(eur("0.75")), eur("0.5").checked_add(&eur("0.25")));
+        assert_eq!(Ok(eur("-0.5")), eur("1.5").checked_sub(&eur("2")));
+        assert_eq!(
+            Ok(eur("3")),
+            Money::sum("EUR".to_string(), &[eur("1"), eur("2")])
+        );
+
+        let sek = Money::new(parse_amount("1").unwrap(), "SEK".to_string());
+        assert_eq!(
+            Err(MoneyError::CurrencyMismatch {
+                expected: "EUR".to_string(),
+                found: "SEK".to_string()
+            }),
+            eur("1").checked_add(&sek)
+        );
+        assert!(Money::sum("EUR".to_string(), &[eur("1"), sek]).is_err());
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_amounts_are_exact() {
+        assert_eq!(Ok(eur("0.3")), eur("0.1").checked_add(&eur("0.2")));
+        assert_eq!("-12.50", eur("-12.50").amount.to_string());
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_local_date_params() {
+        let params = TransactionParams::default()
+            .with_from_booking_datetime(date(2020, 3, 1).and_hms_opt(8, 30, 0).unwrap())
+            .with_to_booking_date(date(2020, 3, 31));
+        assert_eq!(
+            "fromBookingDateTime=2020-03-01T08%3A30%3A00&toBookingDateTime=2020-03-31T00%3A00%3A00",
+            serde_urlencoded::to_string(&params).unwrap()
+        );
+        assert_eq!(
+            "",
+            serde_urlencoded::to_string(TransactionParams::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_booking_month() {
+        let params = TransactionParams::default().with_booking_month(date(2020, 2, 15));
+        assert_eq!(
+            Some(date(2020, 2, 1).and_hms_opt(0, 0, 0).unwrap()),
+            params.from_booking_datetime
+        );
+        assert_eq!(
+            Some(date(2020, 2, 29).and_hms_opt(0, 0, 0).unwrap()),
+            params.to_booking_datetime
+        );
+        assert_eq!(
+            Some((date(2020, 12, 1), date(2020, 12, 31))),
+            time::month_of(date(2020, 12, 31))
+        );
+        assert_eq!(None, time::month_of(NaiveDate::MAX));
+
+        let params = TransactionParams::default().with_booking_month(NaiveDate::MAX);
+        assert_eq!(
+            Some(
+                NaiveDate::MAX
+                    .with_day(1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+            ),
+            params.from_booking_datetime
+        );
+        assert_eq!(None, params.to_booking_datetime);
+
+        let params = TransactionParams::current_month();
+        let today = time::local_today();
+        assert!(params.from_booking_datetime.unwrap().date() <= today);
+        assert!(params.to_booking_datetime.unwrap().date() >= today);
+    }
+
+    #[test]
+    fn test_local_time() {
+        let summer = Utc.with_ymd_and_hms(2020, 6, 30, 21, 30, 0).unwrap();
+        assert_eq!(
+            date(2020, 7, 1).and_hms_opt(0, 30, 0).unwrap(),
+            time::to_local(&summer)
+        );
+        let winter = Utc.with_ymd_and_hms(2020, 12, 31, 22, 30, 0).unwrap();
+        assert_eq!(
+            date(2021, 1, 1).and_hms_opt(0, 30, 0).unwrap(),
+            time::to_local(&winter)
+        );
+        assert_eq!(
+            date(2020, 1, 1).and_hms_opt(23, 59, 59),
+            time::end_of_day(date(2020, 1, 1))
+        );
+        assert_eq!(
+            NaiveDate::MAX.and_hms_opt(23, 59, 59),
+            time::end_of_day(NaiveDate::MAX)
+        );
+    }
+}
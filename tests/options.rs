@@ -0,0 +1,113 @@
+mod common;
+
+#[cfg(test)]
+mod options_tests {
+    use super::common::{FakeTransport, MockResponse};
+    use op_api_sdk::client::Client;
+    use op_api_sdk::options::{Api, Options};
+    use std::thread;
+
+    const HOLDINGS: &str = r#"{
+        "fundHoldings": [],
+        "instrumentHoldings": [],
+        "sumOfAllHoldings": {
+            "marketValue": 0.0,
+            "changeOfValue": 0.0,
+            "subscriptionValue": 0.0,
+            "changeAsPercentage": 0.0
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn test_api_versions() {
+        let transport = FakeTransport::new(vec![
+            MockResponse::json(200, r#"{"accounts": []}"#),
+            MockResponse::json(200, HOLDINGS),
+            MockResponse::json(200, r#"{"accounts": []}"#),
+        ]);
+        let options = Options::new_dev("key".to_string());
+        options.set_base_url("https://op.example".to_string());
+        let client = Client::with_transport(options.clone(), transport.clone());
+
+        // Single client uses default version of each API
+        assert!(client.accounts().await.is_ok());
+        assert!(client.holdings().await.is_ok());
+
+        // Overridden version is used only for the given API
+        options.set_api_version(Api::Accounts, "v4".to_string());
+        assert!(client.accounts().await.is_ok());
+        assert_eq!("v4", options.api_version(Api::Accounts));
+        assert_eq!("v1", options.api_version(Api::Holdings));
+
+        let paths: Vec<String> = transport
+            .requests()
+            .iter()
+            .map(|r| r.url.path().to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "/accounts/v3/accounts",
+                "/holdings/info/v1/holdings",
+                "/accounts/v4/accounts"
+            ],
+            paths
+        );
+
+        options.reset_api_version(Api::Accounts);
+        assert_eq!("v3", options.api_version(Api::Accounts));
+        assert_eq!("v1", Api::Funds.default_version());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_version() {
+        let options = Options::new_dev("key".to_string());
+        assert_eq!("v3", options.version());
+
+        options.set_version("v2".to_string());
+        assert_eq!("v2", options.version());
+        assert_eq!("v2", options.api_version(Api::Accounts));
+        assert_eq!("v2", options.api_version(Api::Funds));
+        assert_eq!("v2", options.api_version(Api::Holdings));
+    }
+
+    #[test]
+    fn test_snapshot_is_immutable() {
+        let options = Options::new("key-1".to_string(), "token-1".to_string());
+        let snapshot = options.snapshot();
+
+        options.set_credentials("key-2".to_string(), "token-2".to_string());
+        options.set_api_version(Api::Funds, "v2".to_string());
+
+        assert_eq!("key-1", snapshot.api_key());
+        assert_eq!("token-1", snapshot.authorization());
+        assert_eq!("v1", snapshot.api_version(Api::Funds));
+
+        let snapshot = options.snapshot();
+        assert_eq!("key-2", snapshot.api_key());
+        assert_eq!("token-2", snapshot.authorization());
+        assert_eq!("v2", snapshot.api_version(Api::Funds));
+    }
+
+    #[test]
+    fn test_snapshot_is_consistent() {
+        let options = Options::new("key-0".to_string(), "token-0".to_string());
+
+        let writer = {
+            let options = options.clone();
+            thread::spawn(move || {
+                for i in 0..1000 {
+                    options.set_credentials(format!("key-{}", i), format!("token-{}", i));
+                }
+            })
+        };
+        for _ in 0..1000 {
+            let snapshot = options.snapshot();
+            assert_eq!(
+                snapshot.api_key().trim_start_matches("key-"),
+                snapshot.authorization().trim_start_matches("token-")
+            );
+        }
+        writer.join().unwrap();
+    }
+}
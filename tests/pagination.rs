@@ -0,0 +1,296 @@
+mod common;
+
+#[cfg(test)]
+mod pagination_tests {
+    use super::common::{transaction_json, transactions_page, FakeTransport, MockResponse};
+    use chrono::{Duration, NaiveDate};
+    use futures::StreamExt;
+    use op_api_sdk::client::Client;
+    use op_api_sdk::error::SdkError;
+    use op_api_sdk::model::accounts::{HistoryOptions, PagingLimits, TransactionParams};
+    use op_api_sdk::options::Options;
+    use std::sync::Arc;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    fn pages() -> Vec<MockResponse> {
+        let t = |id: &str| transaction_json(id, "2020-01-01T10:00:00Z");
+        vec![
+            MockResponse::json(200, &transactions_page(&[t("1"), t("2")], Some("page-2"))),
+            MockResponse::json(200, &transactions_page(&[t("3"), t("4")], Some("page 3"))),
+            MockResponse::json(200, &transactions_page(&[t("5")], None)),
+        ]
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> chrono::NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    fn client(transport: Arc<FakeTransport>) -> Client {
+        let options = Options::new_dev("key".to_string());
+        options.set_base_url("https://op.example".to_string());
+        Client::with_transport(options, transport)
+    }
+
+    #[tokio::test]
+    async fn test_all_pages_are_fetched() {
+        init();
+        let transport = FakeTransport::new(pages());
+        let client = client(transport.clone());
+
+        let params = TransactionParams::default().with_page_size(2);
+        let ids: Vec<String> = client
+            .transactions_stream("acc-1".to_string(), Some(params), PagingLimits::default())
+            .map(|t| t.unwrap().transaction_id)
+            .collect()
+            .await;
+        assert_eq!(vec!["1", "2", "3", "4", "5"], ids);
+
+        let queries: Vec<String> = transport
+            .requests()
+            .iter()
+            .map(|r| r.url.query().unwrap_or("").to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "pageSize=2",
+                "pageSize=2&forwardPagingToken=page-2",
+                "pageSize=2&forwardPagingToken=page+3"
+            ],
+            queries
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pages_are_fetched_lazily() {
+        init();
+        let transport = FakeTransport::new(pages());
+        let client = client(transport.clone());
+
+        let limits = PagingLimits::default().with_max_items(3);
+        let stream = client.transactions_stream("acc-1".to_string(), None, limits);
+        let ids: Vec<String> = stream.map(|t| t.unwrap().transaction_id).collect().await;
+        assert_eq!(vec!["1", "2", "3"], ids);
+        assert_eq!(2, transport.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_page_limit() {
+        init();
+        let transport = FakeTransport::new(pages());
+        let client = client(transport.clone());
+
+        let limits = PagingLimits::default().with_max_pages(1);
+        let stream = client.transactions_stream("acc-1".to_string(), None, limits);
+        assert_eq!(2, stream.collect::<Vec<_>>().await.len());
+        assert_eq!(1, transport.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_after_error() {
+        init();
+        let t = transaction_json("1", "2020-01-01T10:00:00Z");
+        let transport = FakeTransport::new(vec![
+            MockResponse::json(200, &transactions_page(&[t], Some("page-2"))),
+            MockResponse::json(400, ""),
+        ]);
+        let client = client(transport.clone());
+
+        let results: Vec<_> = client
+            .transactions_stream("acc-1".to_string(), None, PagingLimits::default())
+            .collect()
+            .await;
+        assert_eq!(2, results.len());
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert_eq!(2, transport.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_empty_pages_are_skipped() {
+        init();
+        let t = transaction_json("1", "2020-01-01T10:00:00Z");
+        let transport = FakeTransport::new(vec![
+            MockResponse::json(200, &transactions_page(&[], Some("page-2"))),
+            MockResponse::json(200, &transactions_page(&[t], None)),
+        ]);
+        let client = client(transport.clone());
+
+        let ids: Vec<String> = client
+            .transactions_stream("acc-1".to_string(), None, PagingLimits::default())
+            .map(|t| t.unwrap().transaction_id)
+            .collect()
+            .await;
+        assert_eq!(vec!["1"], ids);
+        assert_eq!(2, transport.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_repeated_token_ends_stream() {
+        init();
+        let transport = FakeTransport::new(vec![
+            MockResponse::json(200, &transactions_page(&[], Some("page-2"))),
+            MockResponse::json(200, &transactions_page(&[], Some("page-2"))),
+            MockResponse::json(200, &transactions_page(&[], Some("page-2"))),
+        ]);
+        let client = client(transport.clone());
+
+        let results: Vec<_> = client
+            .transactions_stream("acc-1".to_string(), None, PagingLimits::default())
+            .collect()
+            .await;
+        assert!(results.is_empty());
+        assert_eq!(2, transport.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_token_cycle_ends_stream() {
+        init();
+        let t = |id: &str| transaction_json(id, "2020-01-01T10:00:00Z");
+        let transport = FakeTransport::new(vec![
+            MockResponse::json(200, &transactions_page(&[t("1")], Some("page-a"))),
+            MockResponse::json(200, &transactions_page(&[t("2")], Some("page-b"))),
+            MockResponse::json(200, &transactions_page(&[t("3")], Some("page-a"))),
+            MockResponse::json(200, &transactions_page(&[t("4")], Some("page-b"))),
+        ]);
+        let client = client(transport.clone());
+
+        let ids: Vec<String> = client
+            .transactions_stream("acc-1".to_string(), None, PagingLimits::default())
+            .map(|t| t.unwrap().transaction_id)
+            .collect()
+            .await;
+        assert_eq!(vec!["1", "2", "3"], ids);
+        assert_eq!(3, transport.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_history_is_fetched_in_windows() {
+        init();
+        let t = transaction_json;
+        let transport = FakeTransport::new(vec![
+            MockResponse::json(
+                200,
+                &transactions_page(&[t("2", "2020-01-05T10:00:00Z")], Some("next")),
+            ),
+            MockResponse::json(
+                200,
+                &transactions_page(&[t("1", "2020-01-03T10:00:00Z")], None),
+            ),
+            MockResponse::json(
+                200,
+                &transactions_page(
+                    &[
+                        t("1", "2020-01-03T10:00:00Z"),
+                        t("3", "2020-01-12T10:00:00Z"),
+                    ],
+                    None,
+                ),
+            ),
+            MockResponse::json(200, &transactions_page(&[], None)),
+        ]);
+        let client = client(transport.clone());
+
+        let from = date(2020, 1, 1);
+        let to = date(2020, 1, 26);
+        let options = HistoryOptions::default()
+            .with_window(Duration::days(10))
+            .with_page_size(50);
+        let history = client
+            .transaction_history("acc-1".to_string(), from, to, options)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = history.iter().map(|t| t.transaction_id.as_str()).collect();
+        assert_eq!(vec!["1", "2", "3"], ids);
+
+        let ranges: Vec<(String, String)> = transport
+            .requests()
+            .iter()
+            .filter(|r| !r.url.query().unwrap().contains("forwardPagingToken"))
+            .map(|r| {
+                let param = |name: &str| {
+                    r.url
+                        .query_pairs()
+                        .find(|(n, _)| n == name)
+                        .map(|(_, v)| v.into_owned())
+                        .unwrap()
+                };
+                (param("fromBookingDateTime"), param("toBookingDateTime"))
+            })
+            .collect();
+        assert_eq!(4, transport.requests().len());
+        assert_eq!(
+            vec![
+                ("2020-01-01T00:00:00", "2020-01-11T00:00:00"),
+                ("2020-01-11T00:00:00", "2020-01-21T00:00:00"),
+                ("2020-01-21T00:00:00", "2020-01-26T00:00:00"),
+            ],
+            ranges
+                .iter()
+                .map(|(f, t)| (f.as_str(), t.as_str()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_with_invalid_range() {
+        init();
+        let transport = FakeTransport::new(vec![]);
+        let client = client(transport.clone());
+
+        let from = date(2020, 2, 1);
+        let to = date(2020, 1, 1);
+        let resp = client
+            .transaction_history("acc-1".to_string(), from, to, HistoryOptions::default())
+            .await;
+        assert!(resp.is_err());
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_history_with_large_window() {
+        init();
+        let transport = FakeTransport::new(vec![MockResponse::json(
+            200,
+            &transactions_page(&[transaction_json("1", "2020-01-03T10:00:00Z")], None),
+        )]);
+        let client = client(transport.clone());
+
+        let from = date(2020, 1, 1);
+        let to = date(2020, 1, 26);
+        let options = HistoryOptions::default().with_window(Duration::MAX);
+        let history = client
+            .transaction_history("acc-1".to_string(), from, to, options)
+            .await
+            .unwrap();
+        assert_eq!(1, history.len());
+
+        let requests = transport.requests();
+        assert_eq!(1, requests.len());
+        let query = requests[0].url.query().unwrap();
+        assert!(query.contains("toBookingDateTime=2020-01-26T00%3A00%3A00"));
+    }
+
+    #[tokio::test]
+    async fn test_history_with_invalid_window() {
+        init();
+        let transport = FakeTransport::new(vec![]);
+        let client = client(transport.clone());
+
+        let from = date(2020, 1, 1);
+        let to = date(2020, 1, 26);
+        for window in [Duration::zero(), Duration::days(-1)] {
+            let options = HistoryOptions::default().with_window(window);
+            let resp = client
+                .transaction_history("acc-1".to_string(), from, to, options)
+                .await;
+            assert!(matches!(resp, Err(SdkError::Configuration(_))));
+        }
+        assert!(transport.requests().is_empty());
+    }
+}
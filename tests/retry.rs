@@ -0,0 +1,134 @@
+mod common;
+
+#[cfg(test)]
+mod retry_tests {
+    use super::common::{MockResponse, MockServer};
+    use op_api_sdk::client::Client;
+    use op_api_sdk::options::{Options, RetryOptions};
+    use std::time::Duration;
+
+    const ACCOUNTS: &str = r#"{"accounts": []}"#;
+    const ERRORS: &str =
+        r#"{"errors": [{"id": "1", "level": "error", "type": "busy", "message": "Try again"}]}"#;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    fn client(server: &MockServer, retry: RetryOptions) -> Client {
+        let options = Options::new_dev("key".to_string());
+        options.set_base_url(server.url());
+        options.set_retry_options(retry);
+        Client::new(options)
+    }
+
+    fn fast_retry() -> RetryOptions {
+        RetryOptions::default()
+            .with_initial_backoff(Duration::from_millis(1))
+            .with_max_backoff(Duration::from_millis(5))
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_is_respected() {
+        init();
+        let server = MockServer::start(vec![
+            MockResponse::json(503, ERRORS).with_header("Retry-After", "0"),
+            MockResponse::json(200, ACCOUNTS),
+        ]);
+        let client = client(&server, RetryOptions::default());
+
+        let resp = client.accounts().await;
+        assert!(resp.is_ok(), "{:?}", resp.err());
+        assert_eq!(2, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_retries_are_exhausted() {
+        init();
+        let server = MockServer::start(vec![
+            MockResponse::json(429, ERRORS),
+            MockResponse::json(502, ERRORS),
+            MockResponse::json(504, ERRORS),
+            MockResponse::json(200, ACCOUNTS),
+        ]);
+        let client = client(&server, fast_retry().with_max_attempts(3));
+
+        let resp = client.accounts().await;
+        assert!(resp.is_err());
+        assert_eq!(3, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_status() {
+        init();
+        let server = MockServer::start(vec![
+            MockResponse::json(400, ERRORS),
+            MockResponse::json(200, ACCOUNTS),
+        ]);
+        let client = client(&server, fast_retry());
+
+        let resp = client.accounts().await;
+        assert!(resp.is_err());
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_retries_disabled() {
+        init();
+        let server = MockServer::start(vec![
+            MockResponse::json(503, ERRORS),
+            MockResponse::json(200, ACCOUNTS),
+        ]);
+        let client = client(&server, RetryOptions::disabled());
+
+        let resp = client.accounts().await;
+        assert!(resp.is_err());
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[test]
+    fn test_backoff() {
+        let retry = RetryOptions::default()
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_max_backoff(Duration::from_millis(1000))
+            .with_multiplier(2.0)
+            .with_jitter(0.0);
+        assert_eq!(Duration::from_millis(100), retry.backoff(1));
+        assert_eq!(Duration::from_millis(200), retry.backoff(2));
+        assert_eq!(Duration::from_millis(400), retry.backoff(3));
+        assert_eq!(Duration::from_millis(1000), retry.backoff(10));
+
+        let retry = retry.with_jitter(0.5);
+        for attempt in 1..5 {
+            let delay = retry.backoff(attempt);
+            let max = retry.clone().with_jitter(0.0).backoff(attempt);
+            assert!(delay <= max);
+            assert!(delay >= max / 2);
+        }
+    }
+
+    #[test]
+    fn test_backoff_with_invalid_values() {
+        let retry = RetryOptions::default()
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_max_backoff(Duration::from_millis(1000))
+            .with_jitter(0.0);
+        let negative = retry.clone().with_multiplier(-2.0);
+        assert_eq!(Duration::from_millis(100), negative.backoff(1));
+        assert_eq!(Duration::from_millis(0), negative.backoff(2));
+
+        let nan = retry.clone().with_multiplier(f64::NAN);
+        assert_eq!(Duration::from_millis(0), nan.backoff(2));
+
+        let infinite = retry.clone().with_multiplier(f64::INFINITY);
+        assert_eq!(Duration::from_millis(1000), infinite.backoff(2));
+
+        let jitter = retry.clone().with_multiplier(2.0).with_jitter(f64::NAN);
+        assert_eq!(Duration::from_millis(200), jitter.backoff(2));
+
+        let unbounded = retry
+            .with_multiplier(f64::INFINITY)
+            .with_max_backoff(Duration::MAX);
+        assert_eq!(Duration::MAX, unbounded.backoff(2));
+    }
+}
@@ -0,0 +1,106 @@
+mod common;
+
+#[cfg(test)]
+mod transport_tests {
+    use super::common::{FakeTransport, MockResponse};
+    use op_api_sdk::client::Client;
+    use op_api_sdk::error::{SdkError, TransportError, TransportErrorKind};
+    use op_api_sdk::model::accounts::TransactionParams;
+    use op_api_sdk::options::{Options, RetryOptions};
+    use std::time::Duration;
+
+    const ACCOUNT: &str = r#"{
+        "accountId": "acc-1",
+        "name": "Checking",
+        "nickname": null,
+        "balance": 12.5,
+        "currency": "EUR",
+        "identifierScheme": "IBAN",
+        "identifier": "FI4250001510000023",
+        "servicerScheme": "BIC",
+        "servicerIdentifier": "OKOYFIHH"
+    }"#;
+
+    fn init() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    fn options() -> std::sync::Arc<Options> {
+        let options = Options::new("my-key".to_string(), "my-token".to_string());
+        options.set_base_url("https://op.example".to_string());
+        options
+    }
+
+    #[tokio::test]
+    async fn test_fake_transport() {
+        init();
+        let transport = FakeTransport::new(vec![MockResponse::json(200, ACCOUNT)]);
+        let client = Client::with_transport(options(), transport.clone());
+
+        let resp = client.account("acc-1".to_string()).await;
+        assert!(resp.is_ok(), "{:?}", resp.err());
+        let account = resp.unwrap();
+        assert_eq!("acc-1", account.account_id);
+        assert_eq!(
+            Some("12.5".to_string()),
+            account.balance.map(|b| b.to_string())
+        );
+
+        let requests = transport.requests();
+        assert_eq!(1, requests.len());
+        assert_eq!("GET", requests[0].method.as_str());
+        assert_eq!(
+            "https://op.example/accounts/v3/accounts/acc-1",
+            requests[0].url.as_str()
+        );
+        assert_eq!("my-key", requests[0].headers["x-api-key"]);
+        assert_eq!("Bearer my-token", requests[0].headers["authorization"]);
+    }
+
+    #[tokio::test]
+    async fn test_query_parameters() {
+        init();
+        let transport = FakeTransport::new(vec![MockResponse::json(
+            200,
+            r#"{"transactions": [], "_links": {"next": null}}"#,
+        )]);
+        let client = Client::with_transport(options(), transport.clone());
+
+        let params = TransactionParams::default().with_page_size(5);
+        let resp = client.transactions("acc-1".to_string(), Some(params)).await;
+        assert!(resp.is_ok(), "{:?}", resp.err());
+
+        let url = transport.requests()[0].url.clone();
+        assert_eq!("/accounts/v3/accounts/acc-1/transactions", url.path());
+        assert_eq!(Some("pageSize=5"), url.query());
+    }
+
+    #[tokio::test]
+    async fn test_transport_errors() {
+        init();
+        let transport = FakeTransport::new(vec![MockResponse::json(200, ACCOUNT)]);
+        transport.push_error(TransportError::new(TransportErrorKind::Other, "broken"));
+        let options = options();
+        options.set_retry_options(
+            RetryOptions::default().with_initial_backoff(Duration::from_millis(1)),
+        );
+        let client = Client::with_transport(options, transport.clone());
+
+        // First request succeeds, second fails with non-retryable error
+        assert!(client.account("acc-1".to_string()).await.is_ok());
+        let resp = client.account("acc-1".to_string()).await;
+        match resp {
+            Err(SdkError::Transport(e)) => {
+                assert_eq!(TransportErrorKind::Other, e.kind());
+                assert_eq!("broken", e.to_string());
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        // Connection errors are retried
+        transport.push_error(TransportError::new(TransportErrorKind::Connect, "refused"));
+        transport.push(MockResponse::json(200, ACCOUNT));
+        assert!(client.account("acc-1".to_string()).await.is_ok());
+        assert_eq!(4, transport.requests().len());
+    }
+}